@@ -1,6 +1,6 @@
 use clap::{self, Parser};
 use semver as sv;
-use sv::cmd::{bump, validate};
+use sv::cmd::{bump, compare, config, get, init, next, satisfies, sort, validate};
 use sv::cmd::{Cli, Commands};
 
 fn main() {
@@ -8,10 +8,34 @@ fn main() {
 
     match &cli.command {
         Commands::Validate(args) => {
-            validate::cli::validate(args);
+            validate::cli::validate(args, cli.output);
         }
         Commands::Bump(args) => {
-            bump::cli::bump(args);
+            bump::cli::bump(args, cli.output);
+        }
+        Commands::Next(args) => {
+            next::cli::next(args, cli.output);
+        }
+        Commands::Compare(args) => {
+            compare::cli::compare(args, cli.output);
+        }
+        Commands::Satisfies(args) => {
+            satisfies::cli::satisfies(args, cli.output);
+        }
+        Commands::Sort(args) => {
+            sort::cli::sort(args, cli.output);
+        }
+        Commands::Max(args) => {
+            sort::cli::max(args, cli.output);
+        }
+        Commands::Get(args) => {
+            get::cli::get(args, cli.output);
+        }
+        Commands::Config(args) => {
+            config::cli::config(args, cli.output);
+        }
+        Commands::Init(args) => {
+            init::cli::init(args, cli.output);
         }
     }
 }
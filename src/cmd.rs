@@ -1,10 +1,26 @@
 use clap::{Parser, Subcommand};
 pub mod error;
 pub mod helpers;
+pub mod output;
+use output::OutputFormat;
 pub mod validate;
 use validate::ValidateArgs;
 pub mod bump;
 use bump::BumpArgs;
+pub mod compare;
+use compare::CompareArgs;
+pub mod config;
+use config::ConfigArgs;
+pub mod get;
+use get::GetArgs;
+pub mod init;
+use init::InitArgs;
+pub mod next;
+use next::NextArgs;
+pub mod satisfies;
+use satisfies::SatisfiesArgs;
+pub mod sort;
+use sort::{MaxArgs, SortArgs};
 
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
@@ -12,6 +28,10 @@ use bump::BumpArgs;
 pub struct Cli {
     #[clap(subcommand)]
     pub command: Commands,
+
+    /// Format of the output
+    #[clap(long, global = true, arg_enum, default_value = "text")]
+    pub output: OutputFormat,
 }
 
 #[derive(Subcommand)]
@@ -21,4 +41,30 @@ pub enum Commands {
 
     /// Bumps the version in a file
     Bump(BumpArgs),
+
+    /// Prints the next version without bumping any file
+    Next(NextArgs),
+
+    /// Compares two versions according to semver precedence
+    ///
+    /// Prints `<`, `=` or `>` and exits respectively with code 10, 0 or 11.
+    Compare(CompareArgs),
+
+    /// Checks if the provided version satisfies a requirement
+    Satisfies(SatisfiesArgs),
+
+    /// Sorts versions according to semver precedence
+    Sort(SortArgs),
+
+    /// Prints the greatest version according to semver precedence
+    Max(MaxArgs),
+
+    /// Prints the current version, or any of its parts, from the configuration file
+    Get(GetArgs),
+
+    /// Manages the configuration file
+    Config(ConfigArgs),
+
+    /// Writes a configuration file bumping the versions found in the well-known files
+    Init(InitArgs),
 }
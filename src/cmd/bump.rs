@@ -3,8 +3,9 @@ mod args;
 mod tests;
 use crate::cmd::validate;
 use crate::{config, core, file};
-pub use args::{BumpArgs, FinalizedBumpArgs};
+pub use args::{BumpArgs, FinalizedBumpArgs, FinalizedVersionArgs, VersionArgs};
 use std::collections::HashMap;
+use std::fmt;
 use std::str::FromStr;
 pub mod cli;
 
@@ -14,6 +15,17 @@ pub enum GenericBumpError {
     File(file::FileBumpError),
 }
 
+impl fmt::Display for GenericBumpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GenericBumpError::Version(err) => write!(f, "{}", err),
+            GenericBumpError::File(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for GenericBumpError {}
+
 impl From<core::VersionError> for GenericBumpError {
     fn from(err: core::VersionError) -> GenericBumpError {
         GenericBumpError::Version(err)
@@ -26,6 +38,7 @@ impl From<file::FileBumpError> for GenericBumpError {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn bump(
     current_version: &str,
     last_stable_version: Option<&str>,
@@ -34,41 +47,80 @@ pub fn bump(
     finalize_prerelease: bool,
     files: &HashMap<String, config::FileConfig>,
     bump_prerelease_func: Option<Box<dyn core::ExtensionBumpFunc>>,
+    bump_buildmetadata_func: Option<Box<dyn core::ExtensionBumpFunc>>,
+    buildmetadata_policy: core::BuildMetadataPolicy,
 ) -> Result<core::Version, GenericBumpError> {
+    let (new_version, changes) = compute_bump(
+        current_version,
+        last_stable_version,
+        part,
+        new_prerelease,
+        finalize_prerelease,
+        files,
+        bump_prerelease_func,
+        bump_buildmetadata_func,
+        buildmetadata_policy,
+    )?;
+    file::write_files_contents(&changes)?;
+    Ok(new_version)
+}
+
+/// Computes the next version and the changes to apply to the files without writing anything.
+#[allow(clippy::too_many_arguments)]
+pub fn compute_bump(
+    current_version: &str,
+    last_stable_version: Option<&str>,
+    part: &core::Part,
+    new_prerelease: bool,
+    finalize_prerelease: bool,
+    files: &HashMap<String, config::FileConfig>,
+    bump_prerelease_func: Option<Box<dyn core::ExtensionBumpFunc>>,
+    bump_buildmetadata_func: Option<Box<dyn core::ExtensionBumpFunc>>,
+    buildmetadata_policy: core::BuildMetadataPolicy,
+) -> Result<(core::Version, Vec<file::FileChange>), GenericBumpError> {
     let new_version = next_version(
         current_version,
+        last_stable_version,
         part,
         new_prerelease,
         finalize_prerelease,
         bump_prerelease_func,
+        bump_buildmetadata_func,
+        buildmetadata_policy,
     )?;
 
     let cv = core::Version::from_str(current_version)?;
-    match last_stable_version.map(core::Version::from_str) {
-        Some(lsv) => match lsv {
-            Ok(v) => file::replace_files_contents(&cv, &new_version, Some(&v), files)
-                .map(|()| new_version)
-                .map_err(GenericBumpError::from),
-            Err(err) => Err(GenericBumpError::from(err)),
-        },
-        None => file::replace_files_contents(&cv, &new_version, None, files)
-            .map(|()| new_version)
-            .map_err(GenericBumpError::from),
-    }
+    let lsv = last_stable_version
+        .map(core::Version::from_str)
+        .transpose()?;
+    let changes = file::compute_files_contents(&cv, &new_version, lsv.as_ref(), files)?;
+    Ok((new_version, changes))
 }
 
-fn next_version(
+#[allow(clippy::too_many_arguments)]
+pub fn next_version(
     version: &str,
+    last_stable_version: Option<&str>,
     part: &core::Part,
     new_prerelease: bool,
     finalize_prerelease: bool,
     bump_prerelease_func: Option<Box<dyn core::ExtensionBumpFunc>>,
+    bump_buildmetadata_func: Option<Box<dyn core::ExtensionBumpFunc>>,
+    buildmetadata_policy: core::BuildMetadataPolicy,
 ) -> Result<core::Version, core::VersionError> {
     if !validate::validate(version) {
         Err(core::VersionError::InvalidVersion(version.to_owned()))
     } else {
         let v = core::Version::from_str(version)?;
-        let version_manager = core::VersionManager::with_extension_bump_func(bump_prerelease_func);
+        let last_stable_version = last_stable_version
+            .map(core::Version::from_str)
+            .transpose()?;
+        let version_manager = core::VersionManager::with_params(
+            bump_prerelease_func,
+            bump_buildmetadata_func,
+            buildmetadata_policy,
+        )
+        .with_last_stable_version(last_stable_version);
         if new_prerelease {
             match part {
                 core::Part::Core(core_part) => version_manager
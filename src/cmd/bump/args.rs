@@ -1,10 +1,12 @@
+use crate::cmd::error::ArgumentsError;
 use crate::cmd::helpers;
 use crate::{config, core};
 use clap::Args;
 use std::collections::HashMap;
 
+/// Arguments shared by the commands computing the next version.
 #[derive(Args)]
-pub struct BumpArgs {
+pub struct VersionArgs {
     /// Current version from which to compute the next one
     #[clap(short = 'v', long, display_order = 1)]
     current_version: Option<String>,
@@ -15,7 +17,7 @@ pub struct BumpArgs {
     #[clap(short, long, display_order = 2)]
     part: Option<core::Part>,
 
-    /// Starts a new prerelease for the provided part or increase the current prerelease.
+    /// Starts a new prerelease for the provided part or increase the current prerelease
     ///
     /// This is incompatible with `--finalize-prerelease`.
     #[clap(long, display_order = 3)]
@@ -23,77 +25,202 @@ pub struct BumpArgs {
 
     /// Finalize the current prerelease
     ///
-    /// The argument `-p, --part <PART>` is ignored. This is incompatible with `---prerelease`.
+    /// The argument `-p, --part <PART>` is ignored. This is incompatible with `--new-prerelease`.
     #[clap(long, display_order = 4)]
     finalize_prerelease: bool,
 
-    // TODO: Handle multiple files
-    /// File containing the version to bump
-    #[clap(short, long, display_order = 5)]
-    file: Option<String>,
+    /// Build metadata to set when bumping the "buildmetadata" part
+    ///
+    /// When bumping any other part the build metadata of the current version is dropped, unless
+    /// `--keep-buildmetadata` is provided.
+    #[clap(long, display_order = 5)]
+    buildmetadata: Option<String>,
 
-    /// Path of the configuration file
-    #[clap(short, long, display_order = 6)]
+    /// Keeps the build metadata of the current version when bumping any other part
+    #[clap(long, display_order = 6)]
+    keep_buildmetadata: bool,
+
+    /// Path of the configuration file, looked for in the current directory and its parents if omitted
+    #[clap(short, long, display_order = 8)]
     config: Option<String>,
 }
 
-pub struct FinalizedBumpArgs {
+pub struct FinalizedVersionArgs {
     pub current_version: String,
     pub last_stable_version: Option<String>,
     pub part: core::Part,
     pub new_prerelease: bool,
     pub finalize_prerelease: bool,
     pub bump_prerelease_func: Option<Box<dyn core::ExtensionBumpFunc>>,
+    pub bump_buildmetadata_func: Option<Box<dyn core::ExtensionBumpFunc>>,
+    pub buildmetadata_policy: core::BuildMetadataPolicy,
+}
+
+impl VersionArgs {
+    pub fn get_config(&self) -> Option<String> {
+        self.config.to_owned()
+    }
+
+    pub fn get_required_args(&self) -> Vec<String> {
+        vec!["current_version".to_owned(), "part".to_owned()]
+    }
+
+    pub fn finalize_from_config(&self, config: &config::Config) -> FinalizedVersionArgs {
+        let part = self
+            .part
+            .to_owned()
+            .unwrap_or(config.default_part.to_owned());
+        FinalizedVersionArgs {
+            current_version: config.current_version.to_owned(),
+            last_stable_version: config.last_stable_version.to_owned(),
+            part,
+            new_prerelease: self.new_prerelease,
+            finalize_prerelease: self.finalize_prerelease,
+            bump_prerelease_func: helpers::build_prerelease_func(config),
+            bump_buildmetadata_func: self.buildmetadata.to_owned().map(helpers::build_const_func),
+            buildmetadata_policy: self.buildmetadata_policy(),
+        }
+    }
+
+    pub fn finalize_from_self(&self) -> Option<FinalizedVersionArgs> {
+        match (self.current_version.as_ref(), self.part.as_ref()) {
+            (Some(current_version), Some(part)) => Some(FinalizedVersionArgs {
+                current_version: current_version.to_owned(),
+                last_stable_version: None,
+                part: part.to_owned(),
+                new_prerelease: self.new_prerelease,
+                finalize_prerelease: self.finalize_prerelease,
+                bump_prerelease_func: None,
+                bump_buildmetadata_func: self
+                    .buildmetadata
+                    .to_owned()
+                    .map(helpers::build_const_func),
+                buildmetadata_policy: self.buildmetadata_policy(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Checks that the build metadata is provided if and only if the "buildmetadata" part is bumped.
+    pub fn validate(&self, args: &FinalizedVersionArgs) -> Result<(), ArgumentsError> {
+        let bumps_buildmetadata = !args.finalize_prerelease
+            && args.part == core::Part::Extension(core::ExtensionPart::BuildMetadata);
+        match (bumps_buildmetadata, self.buildmetadata.is_some()) {
+            (true, false) => Err(ArgumentsError::InvalidArguments(
+                "Missing build metadata, it must be provided with `--buildmetadata` to bump the \
+                 \"buildmetadata\" part"
+                    .to_owned(),
+            )),
+            (false, true) => Err(ArgumentsError::InvalidArguments(
+                "`--buildmetadata` can only be provided to bump the \"buildmetadata\" part"
+                    .to_owned(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn buildmetadata_policy(&self) -> core::BuildMetadataPolicy {
+        if self.keep_buildmetadata {
+            core::BuildMetadataPolicy::Keep
+        } else {
+            core::BuildMetadataPolicy::Drop
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct BumpArgs {
+    #[clap(flatten)]
+    version: VersionArgs,
+
+    // TODO: Handle multiple files
+    /// File containing the version to bump
+    #[clap(short, long, display_order = 7)]
+    file: Option<String>,
+
+    /// Prints the changes as unified diffs without writing any file
+    #[clap(long, display_order = 9)]
+    dry_run: bool,
+
+    /// Commits the bumped files with git
+    ///
+    /// Enabled by default if `commit = true` is in `[semver.git]` of the configuration file.
+    #[clap(long, display_order = 10, overrides_with = "no-commit")]
+    commit: bool,
+
+    /// Doesn't commit the bumped files even if enabled in the configuration file
+    #[clap(long, display_order = 11, overrides_with = "commit")]
+    no_commit: bool,
+
+    /// Creates an annotated git tag of the bump, which implies `--commit`
+    ///
+    /// Enabled by default if `tag = true` is in `[semver.git]` of the configuration file.
+    #[clap(long, display_order = 12, overrides_with = "no-tag")]
+    tag: bool,
+
+    /// Doesn't create the git tag even if enabled in the configuration file
+    #[clap(long, display_order = 13, overrides_with = "tag")]
+    no_tag: bool,
+}
+
+impl BumpArgs {
+    /// Overrides the git options of the configuration with the ones provided as arguments.
+    fn git_options(&self, git: config::GitOptions) -> config::GitOptions {
+        let flag = |enabled: bool, disabled: bool, default: bool| (default || enabled) && !disabled;
+        let tag = flag(self.tag, self.no_tag, git.tag);
+        config::GitOptions {
+            commit: flag(self.commit, self.no_commit, git.commit) || tag,
+            tag,
+            ..git
+        }
+    }
+}
+
+pub struct FinalizedBumpArgs {
+    pub version: FinalizedVersionArgs,
     pub files: HashMap<String, config::FileConfig>,
     pub original_config: Option<config::Config>,
+    pub dry_run: bool,
+    pub git: config::GitOptions,
 }
 
 impl helpers::FinalizeArgs for BumpArgs {
     type FinalizedArgs = FinalizedBumpArgs;
 
     fn get_config(&self) -> Option<String> {
-        self.config.to_owned()
+        self.version.get_config()
     }
 
     fn get_required_args(&self) -> Vec<String> {
-        vec![
-            "current_version".to_owned(),
-            "part".to_owned(),
-            "file".to_owned(),
-        ]
+        [self.version.get_required_args(), vec!["file".to_owned()]].concat()
+    }
+
+    fn validate_config(&self, config: &config::Config) -> Result<(), config::ConfigError> {
+        config.validate_all()
+    }
+
+    fn validate_args(&self, args: &Self::FinalizedArgs) -> Result<(), ArgumentsError> {
+        self.version.validate(&args.version)
     }
 
     fn finalize_from_config(&self, config: config::Config) -> Self::FinalizedArgs {
-        let original_config = config.clone();
         FinalizedBumpArgs {
-            current_version: config.current_version,
-            last_stable_version: config.last_stable_version,
-            part: self.part.to_owned().unwrap_or(config.default_part),
-            new_prerelease: self.new_prerelease,
-            finalize_prerelease: self.finalize_prerelease,
-            files: config.files,
-            bump_prerelease_func: config
-                .bump_prerelease_func
-                .map(|code| helpers::build_bump_func(code).unwrap()),
-            original_config: Some(original_config),
+            version: self.version.finalize_from_config(&config),
+            files: config.files.to_owned(),
+            dry_run: self.dry_run,
+            git: self.git_options(config.git.to_owned()),
+            original_config: Some(config),
         }
     }
 
     fn finalize_from_self(&self) -> Option<Self::FinalizedArgs> {
-        match (
-            self.current_version.as_ref(),
-            self.part.as_ref(),
-            self.file.as_ref(),
-        ) {
-            (Some(current_version), Some(part), Some(file)) => Some(FinalizedBumpArgs {
-                current_version: current_version.to_owned(),
-                last_stable_version: None,
-                part: part.to_owned(),
-                new_prerelease: self.new_prerelease,
-                finalize_prerelease: self.finalize_prerelease,
+        match (self.version.finalize_from_self(), self.file.as_ref()) {
+            (Some(version), Some(file)) => Some(FinalizedBumpArgs {
+                version,
                 files: HashMap::from([(file.to_owned(), config::FileConfig::new())]),
-                bump_prerelease_func: None,
                 original_config: None,
+                dry_run: self.dry_run,
+                git: self.git_options(config::GitOptions::default()),
             }),
             _ => None,
         }
@@ -1,36 +1,176 @@
 use super::args::BumpArgs;
-use super::bump as do_bump;
+use super::compute_bump;
 use crate::cmd::error;
 use crate::cmd::helpers::FinalizeArgs;
+use crate::cmd::output::{self, ErrorCode, OutputFormat, VersionOutput};
+use crate::{file, git, template};
+use serde::Serialize;
+use std::path::Path;
 
-pub fn bump(args: &BumpArgs) {
+#[derive(Serialize)]
+struct BumpOutput<'a> {
+    old_version: &'a str,
+    new_version: VersionOutput<'a>,
+    last_stable_version: Option<&'a str>,
+    files: Vec<FileOutput<'a>>,
+    config_file: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    config_diff: Option<String>,
+    dry_run: bool,
+    committed: bool,
+    tag: Option<String>,
+}
+
+#[derive(Serialize)]
+struct FileOutput<'a> {
+    path: &'a str,
+    matches: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<String>,
+}
+
+pub fn bump(args: &BumpArgs, output: OutputFormat) {
     match args.finalize() {
         Ok(config) => {
-            match do_bump(
-                &config.current_version,
-                config.last_stable_version.as_deref(),
-                &config.part,
-                config.new_prerelease,
-                config.finalize_prerelease,
+            match compute_bump(
+                &config.version.current_version,
+                config.version.last_stable_version.as_deref(),
+                &config.version.part,
+                config.version.new_prerelease,
+                config.version.finalize_prerelease,
                 &config.files,
-                config.bump_prerelease_func,
+                config.version.bump_prerelease_func,
+                config.version.bump_buildmetadata_func,
+                config.version.buildmetadata_policy,
             ) {
-                Ok(version) => {
-                    println!("Bumped to version: '{}'", version);
-                    match config.original_config.unwrap().update(&version) {
-                        Ok(_) => std::process::exit(0),
-                        Err(err) => {
-                            println!("Error updating the configuration file: {:?}", err);
+                Ok((version, changes)) => {
+                    let mut all_changes = changes.clone();
+                    if let Some(original_config) = &config.original_config {
+                        if let Err(err) =
+                            original_config.add_file_change(&version, &mut all_changes)
+                        {
+                            match output {
+                                OutputFormat::Text => {
+                                    println!("Error updating the configuration file: {:?}", err)
+                                }
+                                OutputFormat::Json => {
+                                    output::print_json_error("config_io_error", err.to_string())
+                                }
+                            }
                             std::process::exit(2);
                         }
+                    }
+                    let release = if config.dry_run {
+                        None
+                    } else {
+                        let context = template::Context::with_versions(
+                            config.version.current_version.to_owned(),
+                            version.to_string(),
+                            config
+                                .version
+                                .last_stable_version
+                                .to_owned()
+                                .unwrap_or_default(),
+                        );
+                        match git::prepare(Path::new("."), &config.git, &context) {
+                            Ok(release) => release,
+                            Err(err) => {
+                                match output {
+                                    OutputFormat::Text => println!("Error: {}", err),
+                                    OutputFormat::Json => {
+                                        output::print_json_error(err.code(), err.to_string())
+                                    }
+                                }
+                                std::process::exit(1);
+                            }
+                        }
                     };
+                    if !config.dry_run {
+                        if let Err(err) = file::write_files_contents(&all_changes) {
+                            match output {
+                                OutputFormat::Text => println!("Error: {:?}", err),
+                                OutputFormat::Json => {
+                                    output::print_json_error(err.code(), err.to_string())
+                                }
+                            }
+                            std::process::exit(1);
+                        }
+                    }
+                    if let Some(release) = &release {
+                        let paths: Vec<&str> = all_changes
+                            .iter()
+                            .map(|change| change.path.as_str())
+                            .collect();
+                        if let Err(err) = git::commit_and_tag(Path::new("."), &paths, release) {
+                            match output {
+                                OutputFormat::Text => {
+                                    println!("Bumped to version: '{}'", version);
+                                    println!("Error: {}", err)
+                                }
+                                OutputFormat::Json => {
+                                    output::print_json_error(err.code(), err.to_string())
+                                }
+                            }
+                            std::process::exit(1);
+                        }
+                    }
+                    let committed = release.is_some();
+                    let tag = release.and_then(|release| release.tag_name);
+                    match output {
+                        OutputFormat::Text if config.dry_run => {
+                            println!("Would bump to version: '{}'", version);
+                            for change in all_changes {
+                                print!("{}", change.unified_diff());
+                            }
+                        }
+                        OutputFormat::Text => {
+                            println!("Bumped to version: '{}'", version);
+                            if committed {
+                                println!("Committed the bump");
+                            }
+                            if let Some(tag) = &tag {
+                                println!("Tagged as '{}'", tag);
+                            }
+                        }
+                        OutputFormat::Json => output::print_json(&BumpOutput {
+                            old_version: &config.version.current_version,
+                            new_version: VersionOutput::from(&version),
+                            last_stable_version: config.version.last_stable_version.as_deref(),
+                            // The change to the configuration file is either merged into the one
+                            // of the same file or appended after the others
+                            files: changes
+                                .iter()
+                                .zip(&all_changes)
+                                .map(|(change, full_change)| FileOutput {
+                                    path: &change.path,
+                                    matches: change.matches,
+                                    diff: config.dry_run.then(|| full_change.unified_diff()),
+                                })
+                                .collect(),
+                            config_file: config
+                                .original_config
+                                .as_ref()
+                                .and_then(|original_config| original_config.path.as_deref()),
+                            config_diff: all_changes
+                                .get(changes.len())
+                                .filter(|_| config.dry_run)
+                                .map(|change| change.unified_diff()),
+                            dry_run: config.dry_run,
+                            committed,
+                            tag,
+                        }),
+                    }
+                    std::process::exit(0);
                 }
                 Err(err) => {
-                    println!("Error: {:?}", err);
+                    match output {
+                        OutputFormat::Text => println!("Error: {:?}", err),
+                        OutputFormat::Json => output::print_json_error(err.code(), err.to_string()),
+                    }
                     std::process::exit(1);
                 }
             }
         }
-        Err(err) => error::handle_args_error(err),
+        Err(err) => error::handle_args_error(err, output),
     };
 }
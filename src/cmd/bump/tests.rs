@@ -1,7 +1,8 @@
 use super::{bump, GenericBumpError};
 use crate::config::FileConfig;
 use crate::core::{
-    BumpError, CorePart, ExtensionBumpFunc, ExtensionPart, Part, Version, VersionError,
+    BuildMetadataPolicy, BumpContext, BumpError, CorePart, ExtensionBumpFunc, ExtensionPart, Part,
+    Version, VersionError,
 };
 use crate::file::FileBumpError;
 use crate::tests::{v1, v2, v3};
@@ -22,7 +23,7 @@ fn create_versioned_file(path_name: &str, version: &str) -> Result<String, io::E
     }
     match abs_path.to_str() {
         Some(path) => Ok(path.to_owned()),
-        None => Err(io::Error::new(io::ErrorKind::Other, "Missing file path")),
+        None => Err(io::Error::other("Missing file path")),
     }
 }
 
@@ -37,12 +38,16 @@ where
 }
 
 fn get_bump_prerelease_func() -> Box<dyn ExtensionBumpFunc> {
-    Box::new(|v: &Version| {
-        Ok(v.prerelease.as_ref().map_or("dev.1".to_owned(), |value| {
-            let (prefix, num) = value.split_at(4);
-            let num_inc = num.parse::<usize>().unwrap() + 1;
-            format!("{}{}", prefix, num_inc)
-        }))
+    Box::new(|context: &BumpContext| {
+        Ok(context
+            .version
+            .prerelease
+            .as_ref()
+            .map_or("dev.1".to_owned(), |value| {
+                let (prefix, num) = value.split_at(4);
+                let num_inc = num.parse::<usize>().unwrap() + 1;
+                format!("{}{}", prefix, num_inc)
+            }))
     })
 }
 
@@ -322,7 +327,7 @@ mod test_with_extension {
     use super::{
         bump, create_versioned_file, get_bump_prerelease_func, get_test_cases_bump,
         get_test_cases_bump_finalize_prerelease, get_test_cases_bump_new_prerelease, with_test_dir,
-        CorePart, FileConfig, Part,
+        BuildMetadataPolicy, CorePart, FileConfig, Part,
     };
     use std::collections::HashMap;
     use std::fs;
@@ -344,6 +349,8 @@ mod test_with_extension {
                         false,
                         &files,
                         Some(get_bump_prerelease_func()),
+                        None,
+                        BuildMetadataPolicy::default()
                     ),
                     tc.expected
                 );
@@ -373,6 +380,8 @@ mod test_with_extension {
                         false,
                         &files,
                         Some(get_bump_prerelease_func()),
+                        None,
+                        BuildMetadataPolicy::default()
                     ),
                     tc.expected
                 );
@@ -403,6 +412,8 @@ mod test_with_extension {
                         true,
                         &files,
                         Some(get_bump_prerelease_func()),
+                        None,
+                        BuildMetadataPolicy::default()
                     ),
                     tc.expected
                 );
@@ -419,7 +430,8 @@ mod test_with_extension {
 mod test_without_extension {
     use super::{
         bump, create_versioned_file, get_test_cases_bump, get_test_cases_bump_finalize_prerelease,
-        get_test_cases_bump_new_prerelease, with_test_dir, CorePart, FileConfig, Part,
+        get_test_cases_bump_new_prerelease, with_test_dir, BuildMetadataPolicy, CorePart,
+        FileConfig, Part,
     };
     use std::collections::HashMap;
     use std::fs;
@@ -433,7 +445,17 @@ mod test_without_extension {
                 let file_path = create_versioned_file(test_dir_name, &version).unwrap();
                 let files = HashMap::from([(file_path.to_owned(), FileConfig::new())]);
                 assert_eq!(
-                    bump(&version, None, &tc.part, false, false, &files, None),
+                    bump(
+                        &version,
+                        None,
+                        &tc.part,
+                        false,
+                        false,
+                        &files,
+                        None,
+                        None,
+                        BuildMetadataPolicy::default()
+                    ),
                     tc.expected
                 );
                 let file_content = fs::read_to_string(&file_path).unwrap();
@@ -454,7 +476,17 @@ mod test_without_extension {
                 let file_path = create_versioned_file(test_dir_name, &version).unwrap();
                 let files = HashMap::from([(file_path.to_owned(), FileConfig::new())]);
                 assert_eq!(
-                    bump(&version, None, &tc.part, true, false, &files, None,),
+                    bump(
+                        &version,
+                        None,
+                        &tc.part,
+                        true,
+                        false,
+                        &files,
+                        None,
+                        None,
+                        BuildMetadataPolicy::default()
+                    ),
                     tc.expected
                 );
                 let file_content = fs::read_to_string(&file_path).unwrap();
@@ -484,6 +516,8 @@ mod test_without_extension {
                         true,
                         &files,
                         None,
+                        None,
+                        BuildMetadataPolicy::default()
                     ),
                     tc.expected
                 );
@@ -499,8 +533,8 @@ mod test_without_extension {
 
 mod test_generic_errors {
     use super::{
-        bump, create_versioned_file, get_bump_prerelease_func, with_test_dir, CorePart,
-        FileBumpError, FileConfig, GenericBumpError, Part, VersionError,
+        bump, create_versioned_file, get_bump_prerelease_func, with_test_dir, BuildMetadataPolicy,
+        CorePart, FileBumpError, FileConfig, GenericBumpError, Part, VersionError,
     };
     use std::collections::HashMap;
     use std::fs;
@@ -529,6 +563,8 @@ mod test_generic_errors {
                         false,
                         &files,
                         Some(get_bump_prerelease_func()),
+                        None,
+                        BuildMetadataPolicy::default()
                     )
                     .unwrap_err(),
                     GenericBumpError::Version(VersionError::InvalidVersion(_)),
@@ -558,6 +594,8 @@ mod test_generic_errors {
                     false,
                     &files,
                     Some(get_bump_prerelease_func()),
+                    None,
+                    BuildMetadataPolicy::default()
                 )
                 .unwrap_err(),
                 GenericBumpError::File(FileBumpError::NoOp(_))
@@ -585,6 +623,8 @@ mod test_generic_errors {
                     false,
                     &files,
                     Some(get_bump_prerelease_func()),
+                    None,
+                    BuildMetadataPolicy::default()
                 )
                 .unwrap_err(),
                 GenericBumpError::File(FileBumpError::Io(_))
@@ -592,3 +632,120 @@ mod test_generic_errors {
         })
     }
 }
+
+mod test_buildmetadata {
+    use super::{
+        bump, create_versioned_file, with_test_dir, BuildMetadataPolicy, ExtensionPart, FileConfig,
+        Part,
+    };
+    use crate::cmd::helpers::build_const_func;
+    use crate::core::Version;
+    use std::collections::HashMap;
+    use std::fs;
+
+    #[test]
+    fn test_bump_buildmetadata() {
+        let func_name = "test_buildmetadata___test_bump_buildmetadata";
+        with_test_dir(func_name, |test_dir_name| {
+            let version = "1.2.3-dev.1+sha.aaa";
+            let file_path = create_versioned_file(test_dir_name, version).unwrap();
+            let files = HashMap::from([(file_path.to_owned(), FileConfig::new())]);
+            assert_eq!(
+                bump(
+                    version,
+                    None,
+                    &Part::Extension(ExtensionPart::BuildMetadata),
+                    false,
+                    false,
+                    &files,
+                    None,
+                    Some(build_const_func("sha.bbb".to_owned())),
+                    BuildMetadataPolicy::default()
+                ),
+                Ok(Version::with_values_and_buildmetadata(
+                    1,
+                    2,
+                    3,
+                    Some("dev.1".to_owned()),
+                    Some("sha.bbb".to_owned())
+                ))
+            );
+            assert_eq!(
+                fs::read_to_string(&file_path).unwrap(),
+                "Version: '1.2.3-dev.1+sha.bbb'"
+            );
+        });
+    }
+}
+
+mod test_compute_bump {
+    use super::{
+        create_versioned_file, with_test_dir, BuildMetadataPolicy, CorePart, FileConfig, Part,
+    };
+    use crate::cmd::bump::compute_bump;
+    use crate::core::Version;
+    use std::collections::HashMap;
+    use std::fs;
+
+    #[test]
+    fn test_compute_bump() {
+        let func_name = "test_compute_bump___test_compute_bump";
+        with_test_dir(func_name, |test_dir_name| {
+            let file_path = create_versioned_file(test_dir_name, "1.0.0").unwrap();
+            let files = HashMap::from([(file_path.to_owned(), FileConfig::new())]);
+            let (version, changes) = compute_bump(
+                "1.0.0",
+                None,
+                &Part::Core(CorePart::Minor),
+                false,
+                false,
+                &files,
+                None,
+                None,
+                BuildMetadataPolicy::default(),
+            )
+            .unwrap();
+            assert_eq!(version, Version::with_values(1, 1, 0, None));
+            assert_eq!(changes.len(), 1);
+            assert_eq!(changes[0].path, file_path);
+            assert_eq!(changes[0].original_content, "Version: '1.0.0'");
+            assert_eq!(changes[0].new_content, "Version: '1.1.0'");
+            assert_eq!(fs::read_to_string(&file_path).unwrap(), "Version: '1.0.0'");
+        });
+    }
+}
+
+mod test_git_args {
+    use crate::cmd::helpers::FinalizeArgs;
+    use crate::cmd::{Cli, Commands};
+    use crate::config::GitOptions;
+    use clap::Parser;
+
+    fn git_options(args: &[&str]) -> GitOptions {
+        let base_args = [
+            "semver", "bump", "-v", "1.0.0", "-p", "minor", "-f", "VERSION",
+        ];
+        let cli = Cli::try_parse_from([&base_args, args].concat()).unwrap();
+        match cli.command {
+            Commands::Bump(args) => args.finalize().unwrap().git,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_git_args() {
+        let test_cases = vec![
+            (vec![], false, false),
+            (vec!["--commit"], true, false),
+            (vec!["--tag"], true, true),
+            (vec!["--commit", "--no-commit"], false, false),
+            (vec!["--no-commit", "--commit"], true, false),
+            (vec!["--tag", "--no-tag"], false, false),
+        ];
+        for (args, commit, tag) in test_cases {
+            let options = git_options(&args);
+            assert_eq!((options.commit, options.tag), (commit, tag), "{:?}", args);
+            assert_eq!(options.message, GitOptions::default().message);
+        }
+    }
+}
@@ -0,0 +1,23 @@
+mod args;
+#[cfg(test)]
+mod tests;
+use crate::cmd::validate;
+use crate::core;
+pub use args::CompareArgs;
+use std::cmp::Ordering;
+use std::str::FromStr;
+pub mod cli;
+
+pub fn compare(version: &str, other_version: &str) -> Result<Ordering, core::VersionError> {
+    let v = parse_version(version)?;
+    let other_v = parse_version(other_version)?;
+    Ok(v.cmp_precedence(&other_v))
+}
+
+fn parse_version(version: &str) -> Result<core::Version, core::VersionError> {
+    if !validate::validate(version) {
+        Err(core::VersionError::InvalidVersion(version.to_owned()))
+    } else {
+        core::Version::from_str(version)
+    }
+}
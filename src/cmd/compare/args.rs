@@ -0,0 +1,10 @@
+use clap::Args;
+
+#[derive(Args)]
+pub struct CompareArgs {
+    /// Version to compare
+    pub version: String,
+
+    /// Version to compare against
+    pub other_version: String,
+}
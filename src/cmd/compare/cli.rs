@@ -0,0 +1,40 @@
+use super::args::CompareArgs;
+use super::compare as do_compare;
+use crate::cmd::output::{self, ErrorCode, OutputFormat};
+use serde::Serialize;
+use std::cmp::Ordering;
+
+#[derive(Serialize)]
+struct CompareOutput<'a> {
+    version: &'a str,
+    other_version: &'a str,
+    result: &'a str,
+}
+
+pub fn compare(args: &CompareArgs, output: OutputFormat) {
+    match do_compare(&args.version, &args.other_version) {
+        Ok(ordering) => {
+            let (result, exit_code) = match ordering {
+                Ordering::Less => ("<", 10),
+                Ordering::Equal => ("=", 0),
+                Ordering::Greater => (">", 11),
+            };
+            match output {
+                OutputFormat::Text => println!("{}", result),
+                OutputFormat::Json => output::print_json(&CompareOutput {
+                    version: &args.version,
+                    other_version: &args.other_version,
+                    result,
+                }),
+            }
+            std::process::exit(exit_code);
+        }
+        Err(err) => {
+            match output {
+                OutputFormat::Text => println!("Error: {}", err),
+                OutputFormat::Json => output::print_json_error(err.code(), err.to_string()),
+            }
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use super::compare;
+use crate::core::VersionError;
+use std::cmp::Ordering;
+
+#[test]
+fn test_compare() {
+    let test_cases = vec![
+        ("1.0.0", "1.0.0", Ordering::Equal),
+        ("1.0.0", "2.0.0", Ordering::Less),
+        ("2.0.0", "1.0.0", Ordering::Greater),
+        ("1.0.0-rc.1", "1.0.0", Ordering::Less),
+        ("1.0.0-beta.11", "1.0.0-beta.2", Ordering::Greater),
+        ("1.0.0+build.1", "1.0.0+build.2", Ordering::Equal),
+    ];
+    for tc in test_cases {
+        assert_eq!(compare(tc.0, tc.1), Ok(tc.2));
+    }
+}
+
+#[test]
+fn test_compare_invalid_version() {
+    assert_eq!(
+        compare("v1.0.0", "1.0.0"),
+        Err(VersionError::InvalidVersion("v1.0.0".to_owned()))
+    );
+    assert_eq!(
+        compare("1.0.0", "1.0"),
+        Err(VersionError::InvalidVersion("1.0".to_owned()))
+    );
+}
@@ -0,0 +1,20 @@
+mod args;
+#[cfg(test)]
+mod tests;
+use crate::cmd::error::ArgumentsError;
+use crate::cmd::helpers;
+use crate::config;
+pub use args::{CheckArgs, ConfigArgs, ConfigCommands};
+pub mod cli;
+
+/// Loads, without validating it, the configuration file provided either explicitly or through
+/// `SEMVER_CONFIG`, otherwise the one found in the current directory and its parents.
+pub fn load(config_path: Option<String>) -> Result<config::Config, ArgumentsError> {
+    if let Some(config_path) = config_path {
+        return Ok(config::Config::load(&config_path)?);
+    }
+    match helpers::find_config() {
+        Some(config_path) => Ok(helpers::load_rebased_config(&config_path)?),
+        None => Err(ArgumentsError::MissingArguments(vec!["config".to_owned()])),
+    }
+}
@@ -0,0 +1,20 @@
+use clap::{Args, Subcommand};
+
+#[derive(Args)]
+pub struct ConfigArgs {
+    #[clap(subcommand)]
+    pub command: ConfigCommands,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Checks the configuration file reporting all the errors found
+    Check(CheckArgs),
+}
+
+#[derive(Args)]
+pub struct CheckArgs {
+    /// Path of the configuration file, looked for in the current directory and its parents if omitted
+    #[clap(short, long)]
+    pub config: Option<String>,
+}
@@ -0,0 +1,58 @@
+use super::args::{CheckArgs, ConfigArgs, ConfigCommands};
+use super::load;
+use crate::cmd::error;
+use crate::cmd::output::{self, ErrorCode, OutputFormat};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct CheckOutput<'a> {
+    path: Option<&'a str>,
+    valid: bool,
+    errors: Vec<CheckError>,
+}
+
+#[derive(Serialize)]
+struct CheckError {
+    code: &'static str,
+    message: String,
+}
+
+pub fn config(args: &ConfigArgs, output: OutputFormat) {
+    match &args.command {
+        ConfigCommands::Check(args) => check(args, output),
+    }
+}
+
+fn check(args: &CheckArgs, output: OutputFormat) {
+    let config = match load(args.config.to_owned()) {
+        Ok(config) => config,
+        Err(err) => return error::handle_args_error(err, output),
+    };
+    let errors = config.check();
+    let path = config.path.as_deref();
+    match output {
+        OutputFormat::Text if errors.is_empty() => {
+            println!(
+                "Configuration file '{}' is valid!",
+                path.unwrap_or_default()
+            )
+        }
+        OutputFormat::Text => {
+            for err in &errors {
+                println!("Error: {}", err);
+            }
+        }
+        OutputFormat::Json => output::print_json(&CheckOutput {
+            path,
+            valid: errors.is_empty(),
+            errors: errors
+                .iter()
+                .map(|err| CheckError {
+                    code: err.code(),
+                    message: err.to_string(),
+                })
+                .collect(),
+        }),
+    }
+    std::process::exit(if errors.is_empty() { 0 } else { 1 });
+}
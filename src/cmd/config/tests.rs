@@ -0,0 +1,41 @@
+use super::load;
+use crate::cmd::error::ArgumentsError;
+use crate::config::ConfigError;
+use std::fs;
+
+#[test]
+fn test_load_without_validating() {
+    let path = "./__test_load_without_validating.toml";
+    fs::write(
+        path,
+        "[semver]\ncurrent_version = \"1.0\"\ndefault_part = \"minor\"\n",
+    )
+    .unwrap();
+    let config = load(Some(path.to_owned()));
+    fs::remove_file(path).unwrap();
+
+    let errors = config.unwrap().check();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::InvalidVersion(key, _) if key == "semver.current_version"
+    ));
+}
+
+#[test]
+fn test_load_error() {
+    let path = "./__test_load_error.toml";
+    fs::write(
+        path,
+        "[semver]\ncurrent_version = \"1.0.0\"\ndefault_part = \"mayor\"\n",
+    )
+    .unwrap();
+    let config = load(Some(path.to_owned()));
+    fs::remove_file(path).unwrap();
+
+    assert!(matches!(
+        config,
+        Err(ArgumentsError::ConfigError(ConfigError::InvalidPart(key, part)))
+            if key == "semver.default_part" && part == "mayor"
+    ));
+}
@@ -1,3 +1,4 @@
+use crate::cmd::output::{self, ErrorCode, OutputFormat};
 use crate::cmd::Cli;
 use crate::config;
 use clap::{CommandFactory, ErrorKind};
@@ -7,6 +8,7 @@ use std::{error, fmt};
 pub enum ArgumentsError {
     ConfigError(config::ConfigError),
     MissingArguments(Vec<String>),
+    InvalidArguments(String),
 }
 
 impl From<config::ConfigError> for ArgumentsError {
@@ -19,14 +21,23 @@ impl fmt::Display for ArgumentsError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ArgumentsError::ConfigError(err) => write!(f, "{}", err),
-            ArgumentsError::MissingArguments(err) => write!(f, "{:#?}", err),
+            ArgumentsError::MissingArguments(missings) => write!(
+                f,
+                "When configuration is not provided these are mandatory: {}",
+                missings.join(", ")
+            ),
+            ArgumentsError::InvalidArguments(msg) => write!(f, "{}", msg),
         }
     }
 }
 
 impl error::Error for ArgumentsError {}
 
-pub fn handle_args_error(err: ArgumentsError) {
+pub fn handle_args_error(err: ArgumentsError, output: OutputFormat) {
+    if output == OutputFormat::Json {
+        output::print_json_error(err.code(), err.to_string());
+        std::process::exit(2);
+    }
     let mut cmd = Cli::command();
     match err {
         ArgumentsError::ConfigError(err) => {
@@ -43,5 +54,8 @@ pub fn handle_args_error(err: ArgumentsError) {
             )
             .exit();
         }
+        ArgumentsError::InvalidArguments(msg) => {
+            cmd.error(ErrorKind::ArgumentConflict, msg).exit();
+        }
     }
 }
@@ -0,0 +1,78 @@
+mod args;
+#[cfg(test)]
+mod tests;
+use crate::{config, core, template};
+pub use args::{Field, FinalizedGetArgs, GetArgs};
+use serde::Serialize;
+use std::str::FromStr;
+use std::{error, fmt};
+pub mod cli;
+
+#[derive(Debug, PartialEq)]
+pub enum GetError {
+    Version(core::VersionError),
+    Template(String),
+}
+
+impl fmt::Display for GetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GetError::Version(err) => write!(f, "{}", err),
+            GetError::Template(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl error::Error for GetError {}
+
+impl From<core::VersionError> for GetError {
+    fn from(err: core::VersionError) -> GetError {
+        GetError::Version(err)
+    }
+}
+
+#[derive(Serialize)]
+struct FormatContext {
+    current_version: String,
+    last_stable_version: String,
+    major: usize,
+    minor: usize,
+    patch: usize,
+    prerelease: String,
+    buildmetadata: String,
+}
+
+/// Returns the value of the field of the current version, or renders `format` if provided.
+///
+/// The format accepts as variables `current_version`, `last_stable_version`, `major`, `minor`,
+/// `patch`, `prerelease` and `buildmetadata`. Missing values are rendered as empty strings.
+pub fn get(
+    config: &config::Config,
+    field: &Field,
+    format: Option<&str>,
+) -> Result<String, GetError> {
+    let version = core::Version::from_str(&config.current_version)?;
+    let context = FormatContext {
+        current_version: config.current_version.to_owned(),
+        last_stable_version: config.last_stable_version.to_owned().unwrap_or_default(),
+        major: version.major,
+        minor: version.minor,
+        patch: version.patch,
+        prerelease: version.prerelease.to_owned().unwrap_or_default(),
+        buildmetadata: version.buildmetadata.to_owned().unwrap_or_default(),
+    };
+    match format {
+        Some(format) => {
+            template::render(format, &context).map_err(|err| GetError::Template(err.to_string()))
+        }
+        None => Ok(match field {
+            Field::CurrentVersion => context.current_version,
+            Field::LastStableVersion => context.last_stable_version,
+            Field::Major => context.major.to_string(),
+            Field::Minor => context.minor.to_string(),
+            Field::Patch => context.patch.to_string(),
+            Field::Prerelease => context.prerelease,
+            Field::Buildmetadata => context.buildmetadata,
+        }),
+    }
+}
@@ -0,0 +1,63 @@
+use crate::cmd::helpers;
+use crate::config;
+use clap::Args;
+
+#[derive(Debug, Clone, Eq, PartialEq, clap::ArgEnum)]
+#[clap(rename_all = "snake_case")]
+pub enum Field {
+    CurrentVersion,
+    LastStableVersion,
+    Major,
+    Minor,
+    Patch,
+    Prerelease,
+    Buildmetadata,
+}
+
+#[derive(Args)]
+pub struct GetArgs {
+    /// Which value to get
+    #[clap(arg_enum, default_value = "current_version")]
+    field: Field,
+
+    /// Template to render instead of a single value (e.g. `v{major}.{minor}`)
+    ///
+    /// The available variables are `current_version`, `last_stable_version`, `major`, `minor`,
+    /// `patch`, `prerelease` and `buildmetadata`.
+    #[clap(long, display_order = 1)]
+    format: Option<String>,
+
+    /// Path of the configuration file, looked for in the current directory and its parents if omitted
+    #[clap(short, long, display_order = 2)]
+    config: Option<String>,
+}
+
+pub struct FinalizedGetArgs {
+    pub field: Field,
+    pub format: Option<String>,
+    pub config: config::Config,
+}
+
+impl helpers::FinalizeArgs for GetArgs {
+    type FinalizedArgs = FinalizedGetArgs;
+
+    fn get_config(&self) -> Option<String> {
+        self.config.to_owned()
+    }
+
+    fn get_required_args(&self) -> Vec<String> {
+        vec!["config".to_owned()]
+    }
+
+    fn finalize_from_config(&self, config: config::Config) -> Self::FinalizedArgs {
+        FinalizedGetArgs {
+            field: self.field.to_owned(),
+            format: self.format.to_owned(),
+            config,
+        }
+    }
+
+    fn finalize_from_self(&self) -> Option<Self::FinalizedArgs> {
+        None
+    }
+}
@@ -0,0 +1,33 @@
+use super::args::GetArgs;
+use super::get as do_get;
+use crate::cmd::error;
+use crate::cmd::helpers::FinalizeArgs;
+use crate::cmd::output::{self, ErrorCode, OutputFormat};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct GetOutput<'a> {
+    value: &'a str,
+}
+
+pub fn get(args: &GetArgs, output: OutputFormat) {
+    match args.finalize() {
+        Ok(args) => match do_get(&args.config, &args.field, args.format.as_deref()) {
+            Ok(value) => {
+                match output {
+                    OutputFormat::Text => println!("{}", value),
+                    OutputFormat::Json => output::print_json(&GetOutput { value: &value }),
+                }
+                std::process::exit(0);
+            }
+            Err(err) => {
+                match output {
+                    OutputFormat::Text => println!("Error: {}", err),
+                    OutputFormat::Json => output::print_json_error(err.code(), err.to_string()),
+                }
+                std::process::exit(1);
+            }
+        },
+        Err(err) => error::handle_args_error(err, output),
+    }
+}
@@ -0,0 +1,69 @@
+use super::{get, Field, GetError};
+use crate::config::Config;
+use crate::core::VersionError;
+use std::str::FromStr;
+
+fn build_config(current_version: &str) -> Config {
+    Config::from_str(&format!(
+        r#"
+[semver]
+current_version = "{}"
+last_stable_version = "1.2.0"
+default_part = "minor"
+"#,
+        current_version
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_get_field() {
+    let config = build_config("1.3.0-dev.2+sha.abc");
+    let test_cases = vec![
+        (Field::CurrentVersion, "1.3.0-dev.2+sha.abc"),
+        (Field::LastStableVersion, "1.2.0"),
+        (Field::Major, "1"),
+        (Field::Minor, "3"),
+        (Field::Patch, "0"),
+        (Field::Prerelease, "dev.2"),
+        (Field::Buildmetadata, "sha.abc"),
+    ];
+    for tc in test_cases {
+        assert_eq!(get(&config, &tc.0, None), Ok(tc.1.to_owned()));
+    }
+
+    let config = build_config("1.3.0");
+    assert_eq!(get(&config, &Field::Prerelease, None), Ok("".to_owned()));
+}
+
+#[test]
+fn test_get_format() {
+    let config = build_config("1.3.0-dev.2");
+    assert_eq!(
+        get(&config, &Field::CurrentVersion, Some("v{major}.{minor}")),
+        Ok("v1.3".to_owned())
+    );
+    assert_eq!(
+        get(
+            &config,
+            &Field::CurrentVersion,
+            Some("{current_version} (last stable: {last_stable_version})")
+        ),
+        Ok("1.3.0-dev.2 (last stable: 1.2.0)".to_owned())
+    );
+    assert!(matches!(
+        get(&config, &Field::CurrentVersion, Some("{unknown}")),
+        Err(GetError::Template(_))
+    ));
+}
+
+#[test]
+fn test_get_invalid_version() {
+    let config = build_config("1.3");
+    assert_eq!(
+        get(&config, &Field::Major, None),
+        Err(GetError::Version(VersionError::InvalidVersion(
+            "1.3".to_owned()
+        )))
+    );
+}
@@ -1,7 +1,21 @@
+#[cfg(test)]
+mod tests;
 use super::error::ArgumentsError;
 use crate::config;
 use crate::core;
-use js_sandbox::{AnyError, Script};
+#[cfg(feature = "js")]
+use chrono::{Local, Utc};
+#[cfg(feature = "js")]
+use js_sandbox::Script;
+#[cfg(feature = "js")]
+use serde::Serialize;
+#[cfg(feature = "js")]
+use std::cell::RefCell;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use std::{env, thread};
 
 pub trait FinalizeArgs {
     type FinalizedArgs;
@@ -14,25 +28,245 @@ pub trait FinalizeArgs {
 
     fn finalize_from_self(&self) -> Option<Self::FinalizedArgs>;
 
+    /// Validates the configuration file, by default without checking that the files exist and the
+    /// prerelease script as not every subcommand uses them.
+    fn validate_config(&self, config: &config::Config) -> Result<(), config::ConfigError> {
+        config.validate()
+    }
+
+    /// Validates the finalized arguments, e.g. the ones that depend on the configuration file.
+    fn validate_args(&self, _args: &Self::FinalizedArgs) -> Result<(), ArgumentsError> {
+        Ok(())
+    }
+
+    /// Finalizes the arguments from the configuration file if provided explicitly, otherwise from
+    /// the arguments themselves. If these are not enough, the configuration file is taken from
+    /// `SEMVER_CONFIG` or looked for in the current directory and its parents.
     fn finalize(&self) -> Result<Self::FinalizedArgs, ArgumentsError> {
-        match self.get_config().as_ref() {
-            Some(config_path) => {
-                let config = config::Config::from_file(config_path)?;
-                Ok(self.finalize_from_config(config))
+        let finalized_args = if let Some(config_path) = self.get_config() {
+            let config = config::Config::load(&config_path)?;
+            self.validate_config(&config)?;
+            self.finalize_from_config(config)
+        } else if let Some(finalized_args) = self.finalize_from_self() {
+            finalized_args
+        } else {
+            match find_config() {
+                Some(config_path) => {
+                    let config = load_rebased_config(&config_path)?;
+                    self.validate_config(&config)?;
+                    self.finalize_from_config(config)
+                }
+                None => return Err(ArgumentsError::MissingArguments(self.get_required_args())),
             }
-            None => {
-                let required_args = self.get_required_args();
-                self.finalize_from_self()
-                    .ok_or(ArgumentsError::MissingArguments(required_args))
+        };
+        self.validate_args(&finalized_args)?;
+        Ok(finalized_args)
+    }
+}
+
+/// Returns the path of the configuration file from `SEMVER_CONFIG` if set, otherwise the one
+/// found in the current directory and its parents.
+pub fn find_config() -> Option<PathBuf> {
+    let env_config_path = env::var(config::CONFIG_ENV_VAR)
+        .ok()
+        .filter(|path| !path.is_empty());
+    env_config_path.map(PathBuf::from).or_else(discover_config)
+}
+
+/// Looks for the configuration file in the current directory and its parents.
+pub fn discover_config() -> Option<PathBuf> {
+    env::current_dir()
+        .ok()
+        .and_then(|dir| config::discover(&dir))
+}
+
+/// Loads, without validating it, a configuration file whose files are relative to its directory,
+/// like a discovered one or one from `SEMVER_CONFIG`. The files of an explicit path are instead
+/// relative to the current directory.
+pub fn load_rebased_config(config_path: &Path) -> Result<config::Config, config::ConfigError> {
+    let mut config = config::Config::load(&config_path.to_string_lossy())?;
+    config.rebase_paths(config_path.parent().unwrap_or_else(|| Path::new("")));
+    Ok(config)
+}
+
+/// Builds the function bumping the prerelease of the configuration through either the script, the
+/// command or the built-in strategy.
+pub fn build_prerelease_func(config: &config::Config) -> Option<Box<dyn core::ExtensionBumpFunc>> {
+    match (
+        &config.bump_prerelease_func,
+        &config.bump_prerelease_command,
+        &config.prerelease_strategy,
+    ) {
+        #[cfg(feature = "js")]
+        (Some(code), _, _) => Some(build_bump_func(code.to_owned())),
+        (_, Some(command), _) => Some(build_command_func(command.to_owned())),
+        (_, None, Some(strategy)) => Some(strategy.bump_func()),
+        _ => None,
+    }
+}
+
+/// Defines the function called in place of `bump`, passing it the context as second argument.
+#[cfg(feature = "js")]
+const SCRIPT_ENTRYPOINT: &str =
+    "function __semverBump(input) { return bump(input.version, input.context); }";
+
+/// Values passed to the `bump` function of a script together with the version.
+#[cfg(feature = "js")]
+#[derive(Debug, Clone, Serialize)]
+pub struct ScriptContext {
+    pub part: String,
+    pub last_stable_version: Option<String>,
+    pub new_prerelease: bool,
+    pub utc_today_ymd: String,
+    pub local_today_ymd: String,
+}
+
+#[cfg(feature = "js")]
+impl From<&core::BumpContext> for ScriptContext {
+    fn from(context: &core::BumpContext) -> Self {
+        ScriptContext {
+            part: context.part.to_string(),
+            last_stable_version: context
+                .last_stable_version
+                .as_ref()
+                .map(|version| version.to_string()),
+            new_prerelease: context.new_prerelease,
+            utc_today_ymd: Utc::now().format("%Y-%m-%d").to_string(),
+            local_today_ymd: Local::now().format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "js")]
+#[derive(Serialize)]
+struct ScriptInput<'a> {
+    version: &'a core::Version,
+    context: &'a ScriptContext,
+}
+
+/// Builds a function calling `bump` of the script with the version and the context.
+///
+/// The script is evaluated on the first call only, and its runtime is reused by the following
+/// ones for as long as the function lives.
+#[cfg(feature = "js")]
+pub fn build_bump_func(code: String) -> Box<dyn core::ExtensionBumpFunc> {
+    let code = format!("{}\n{}", code, SCRIPT_ENTRYPOINT);
+    let script: RefCell<Option<Script>> = RefCell::new(None);
+    Box::new(move |context| {
+        let mut script = script.borrow_mut();
+        if script.is_none() {
+            *script = Some(Script::from_string(&code)?);
+        }
+        let input = ScriptInput {
+            version: &context.version,
+            context: &ScriptContext::from(context),
+        };
+        script
+            .as_mut()
+            .unwrap()
+            .call("__semverBump", &input)
+            .map_err(core::BumpError::from)
+    })
+}
+
+/// Builds a function running the command with the version as JSON on its standard input, and
+/// returning its standard output as the new value.
+///
+/// The rest of the context is passed through the `SEMVER_PART`, `SEMVER_LAST_STABLE_VERSION` and
+/// `SEMVER_NEW_PRERELEASE` environment variables.
+pub fn build_command_func(command: config::BumpCommand) -> Box<dyn core::ExtensionBumpFunc> {
+    Box::new(move |context| {
+        let envs = [
+            ("SEMVER_PART", context.part.to_string()),
+            (
+                "SEMVER_LAST_STABLE_VERSION",
+                context
+                    .last_stable_version
+                    .as_ref()
+                    .map_or(String::new(), |version| version.to_string()),
+            ),
+            ("SEMVER_NEW_PRERELEASE", context.new_prerelease.to_string()),
+        ];
+        run_command(
+            &command,
+            &serde_json::to_string(&context.version).unwrap(),
+            &envs,
+        )
+        .map_err(core::BumpError::CommandError)
+    })
+}
+
+fn run_command(
+    command: &config::BumpCommand,
+    input: &str,
+    envs: &[(&str, String)],
+) -> Result<String, String> {
+    let command_line = command.args.join(" ");
+    let (program, args) = command
+        .args
+        .split_first()
+        .ok_or_else(|| "Missing the program to run".to_owned())?;
+    let mut child = Command::new(program)
+        .args(args)
+        .envs(envs.iter().map(|(key, value)| (key, value)))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Cannot run command '{}': {}", command_line, err))?;
+
+    // The pipes are handled in their own threads to not block on a command reading or writing
+    // more than their buffers
+    let mut stdin = child.stdin.take().unwrap();
+    let input = input.to_owned();
+    // The command is free to not read its input
+    thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let read = |mut pipe: Box<dyn Read + Send>| {
+        thread::spawn(move || {
+            let mut output = String::new();
+            pipe.read_to_string(&mut output).map(|_| output)
+        })
+    };
+    let stdout = read(Box::new(child.stdout.take().unwrap()));
+    let stderr = read(Box::new(child.stderr.take().unwrap()));
+
+    let deadline = Instant::now() + command.timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "Command '{}' timed out after {:?}",
+                    command_line, command.timeout
+                ));
             }
+            Ok(None) => thread::sleep(Duration::from_millis(10)),
+            Err(err) => return Err(format!("Cannot run command '{}': {}", command_line, err)),
         }
+    };
+
+    let invalid_output =
+        |err: std::io::Error| format!("Invalid output of command '{}': {}", command_line, err);
+    let stdout = stdout.join().unwrap().map_err(invalid_output)?;
+    let stderr = stderr.join().unwrap().unwrap_or_default();
+    if !status.success() {
+        let details = match stderr.trim() {
+            "" => String::new(),
+            stderr => format!(": {}", stderr),
+        };
+        return Err(format!(
+            "Command '{}' failed with {}{}",
+            command_line, status, details
+        ));
+    }
+    match stdout.trim() {
+        "" => Err(format!("Command '{}' printed nothing", command_line)),
+        value => Ok(value.to_owned()),
     }
 }
 
-pub fn build_bump_func(code: String) -> Result<Box<dyn core::ExtensionBumpFunc>, AnyError> {
-    let func: Box<dyn core::ExtensionBumpFunc> = Box::new(move |version| {
-        let mut script = Script::from_string(&code)?;
-        script.call("bump", &version).map_err(core::BumpError::from)
-    });
-    Ok(func)
+pub fn build_const_func(value: String) -> Box<dyn core::ExtensionBumpFunc> {
+    Box::new(move |_| Ok(value.to_owned()))
 }
@@ -0,0 +1,196 @@
+use super::load_rebased_config;
+use crate::config::Config;
+#[cfg(feature = "js")]
+use crate::core::{BumpContext, CorePart, Part, Version};
+use std::fs;
+use std::path::Path;
+
+// The commands are run through `sh`
+#[cfg(unix)]
+mod test_command_func {
+    use super::super::build_command_func;
+    use crate::config::BumpCommand;
+    use crate::core::{
+        BumpContext, BumpError, CorePart, ExtensionPart, Part, Version, VersionManager,
+    };
+    use std::time::Duration;
+
+    fn context(version: Version) -> BumpContext {
+        BumpContext {
+            version,
+            part: Part::Extension(ExtensionPart::Prerelease),
+            last_stable_version: None,
+            new_prerelease: false,
+        }
+    }
+
+    fn bump_with_command(script: &str, timeout: Duration) -> Result<String, BumpError> {
+        let func = build_command_func(BumpCommand {
+            args: vec!["sh".to_owned(), "-c".to_owned(), script.to_owned()],
+            timeout,
+        });
+        func(&context(Version::with_values(
+            1,
+            2,
+            3,
+            Some("dev.1".to_owned()),
+        )))
+    }
+
+    #[test]
+    fn test_command_func() {
+        let timeout = Duration::from_secs(10);
+        assert_eq!(
+            bump_with_command("echo dev.2", timeout),
+            Ok("dev.2".to_owned())
+        );
+        assert_eq!(
+            bump_with_command("cat", timeout),
+            Ok(
+                r#"{"major":1,"minor":2,"patch":3,"prerelease":"dev.1","buildmetadata":null}"#
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn test_command_func_error() {
+        let timeout = Duration::from_secs(10);
+        let test_cases = vec![
+            (
+                "echo 'not a prerelease' >&2; exit 3",
+                "Command 'sh -c echo 'not a prerelease' >&2; exit 3' failed with exit status: 3: not a prerelease",
+            ),
+            ("exit 1", "Command 'sh -c exit 1' failed with exit status: 1"),
+            ("true", "Command 'sh -c true' printed nothing"),
+        ];
+        for (script, expected) in test_cases {
+            assert_eq!(
+                bump_with_command(script, timeout),
+                Err(BumpError::CommandError(expected.to_owned()))
+            );
+        }
+
+        assert_eq!(
+            bump_with_command("sleep 5", Duration::from_millis(100)),
+            Err(BumpError::CommandError(
+                "Command 'sh -c sleep 5' timed out after 100ms".to_owned()
+            ))
+        );
+
+        let func = build_command_func(BumpCommand {
+            args: vec!["./__missing_command.sh".to_owned()],
+            timeout,
+        });
+        assert!(matches!(
+            func(&context(Version::new())),
+            Err(BumpError::CommandError(msg))
+                if msg.starts_with("Cannot run command './__missing_command.sh': ")
+        ));
+    }
+
+    #[test]
+    fn test_command_func_invalid_prerelease() {
+        let vm = VersionManager::with_extension_bump_func(Some(build_command_func(BumpCommand {
+            args: vec![
+                "sh".to_owned(),
+                "-c".to_owned(),
+                "echo 'not valid!'".to_owned(),
+            ],
+            timeout: Duration::from_secs(10),
+        })));
+        assert_eq!(
+            vm.bump(
+                &Version::with_values(1, 0, 0, Some("dev.1".to_owned())),
+                &Part::Extension(ExtensionPart::Prerelease)
+            ),
+            Err(BumpError::InvalidOperation(
+                "Invalid prerelease 'not valid!'".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_command_func_env() {
+        let func = build_command_func(BumpCommand {
+            args: vec![
+                "sh".to_owned(),
+                "-c".to_owned(),
+                "echo $SEMVER_PART-$SEMVER_LAST_STABLE_VERSION-$SEMVER_NEW_PRERELEASE".to_owned(),
+            ],
+            timeout: Duration::from_secs(10),
+        });
+        assert_eq!(
+            func(&BumpContext {
+                version: Version::with_values(1, 3, 0, None),
+                part: Part::Core(CorePart::Minor),
+                last_stable_version: Some(Version::with_values(1, 2, 0, None)),
+                new_prerelease: true,
+            }),
+            Ok("minor-1.2.0-true".to_owned())
+        );
+    }
+}
+
+#[cfg(feature = "js")]
+#[test]
+fn test_script_context() {
+    let context = super::ScriptContext::from(&BumpContext {
+        version: Version::with_values(1, 3, 0, None),
+        part: Part::Core(CorePart::Minor),
+        last_stable_version: Some(Version::with_values(1, 2, 0, None)),
+        new_prerelease: true,
+    });
+    assert_eq!(context.part, "minor");
+    assert_eq!(context.last_stable_version, Some("1.2.0".to_owned()));
+    assert!(context.new_prerelease);
+    assert_eq!(context.utc_today_ymd.len(), "2022-03-01".len());
+}
+
+#[test]
+fn test_load_rebased_config() {
+    let dir = "./__test_load_rebased_config";
+    fs::create_dir_all(dir).unwrap();
+    let config_path = format!("{}/.semver.toml", dir);
+    fs::write(
+        &config_path,
+        "[semver]\ncurrent_version = \"1.0.0\"\ndefault_part = \"minor\"\n[semver.files.\"f.txt\"]\n",
+    )
+    .unwrap();
+    let file_paths = |config: Config| config.files.keys().cloned().collect::<Vec<String>>();
+
+    // An explicit path keeps the files relative to the current directory
+    assert_eq!(
+        file_paths(Config::load(&config_path).unwrap()),
+        vec!["f.txt".to_owned()]
+    );
+    assert_eq!(
+        file_paths(load_rebased_config(Path::new(&config_path)).unwrap()),
+        vec![format!("{}/f.txt", dir)]
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(feature = "js")]
+#[test]
+fn test_bump_func() {
+    // The counter is kept across the calls as the script is evaluated only once
+    let func = super::build_bump_func(
+        r#"
+var calls = 0;
+function bump(version, context) {
+  calls += 1;
+  return `${context.part}.${context.last_stable_version.replace(/\./g, "-")}.${calls}`;
+}
+"#
+        .to_owned(),
+    );
+    let context = BumpContext {
+        version: Version::with_values(1, 3, 0, None),
+        part: Part::Core(CorePart::Minor),
+        last_stable_version: Some(Version::with_values(1, 2, 0, None)),
+        new_prerelease: true,
+    };
+    assert_eq!(func(&context), Ok("minor.1-2-0.1".to_owned()));
+    assert_eq!(func(&context), Ok("minor.1-2-0.2".to_owned()));
+}
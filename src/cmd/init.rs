@@ -0,0 +1,241 @@
+mod args;
+#[cfg(test)]
+mod tests;
+use crate::config::FileFormat;
+use crate::core;
+use crate::file::structured;
+pub use args::InitArgs;
+use std::path::Path;
+use std::str::FromStr;
+use std::{fmt, fs, io};
+pub mod cli;
+
+/// Name of the configuration file written by `init`.
+pub const CONFIG_FILE_NAME: &str = ".semver.toml";
+
+/// Files whose version is detected, by precedence.
+const VERSION_FILES: [&str; 4] = ["Cargo.toml", "package.json", "pyproject.toml", "VERSION"];
+
+/// Default prerelease script, producing prereleases like `dev.1`, `dev.2` and so on.
+#[cfg(feature = "js")]
+const PRERELEASE_CONFIG: &str = r#"bump_script = '''
+var PREFIX = "dev.";
+function bump(version) {
+  var counter = !version.prerelease ? 0 : parseInt(version.prerelease.slice(PREFIX.length));
+  return `${PREFIX}${counter + 1}`;
+}
+'''
+"#;
+
+/// Default prerelease strategy, equivalent to the script that cannot be run without `js`.
+#[cfg(not(feature = "js"))]
+const PRERELEASE_CONFIG: &str = "strategy = \"numeric\"\nprefix = \"dev.\"\n";
+
+#[derive(Debug)]
+pub enum InitError {
+    Io(io::Error),
+    AlreadyExists(String),
+    UndetectedVersion(String),
+    InvalidVersion(core::VersionError),
+}
+
+impl fmt::Display for InitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InitError::Io(err) => write!(f, "{}", err),
+            InitError::AlreadyExists(msg) => write!(f, "{}", msg),
+            InitError::UndetectedVersion(msg) => write!(f, "{}", msg),
+            InitError::InvalidVersion(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for InitError {}
+
+impl PartialEq for InitError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (InitError::AlreadyExists(a), InitError::AlreadyExists(b)) => a == b,
+            (InitError::UndetectedVersion(a), InitError::UndetectedVersion(b)) => a == b,
+            (InitError::InvalidVersion(a), InitError::InvalidVersion(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl From<io::Error> for InitError {
+    fn from(err: io::Error) -> InitError {
+        InitError::Io(err)
+    }
+}
+
+impl From<core::VersionError> for InitError {
+    fn from(err: core::VersionError) -> InitError {
+        InitError::InvalidVersion(err)
+    }
+}
+
+/// A file whose version has been detected, with the templates to bump it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedFile {
+    pub path: String,
+    pub version: String,
+    /// `None` if the whole content of the file is the version.
+    pub search: Option<String>,
+    pub replace: Option<String>,
+    /// Whether `search` is also found after the version, e.g. in the dependencies.
+    pub first_only: bool,
+}
+
+/// The scaffolded configuration together with the files it bumps and the ones skipped because
+/// their version differs from the current one.
+#[derive(Debug, PartialEq)]
+pub struct Scaffold {
+    pub current_version: String,
+    pub files: Vec<DetectedFile>,
+    pub skipped_files: Vec<DetectedFile>,
+    pub content: String,
+}
+
+/// Builds a configuration bumping the versions found in the well-known files of `dir`.
+///
+/// The current version is the one provided, otherwise the first one detected among `Cargo.toml`,
+/// `package.json`, `pyproject.toml` and `VERSION`.
+pub fn scaffold(
+    dir: &Path,
+    current_version: Option<&str>,
+    default_part: &core::Part,
+    prerelease: bool,
+) -> Result<Scaffold, InitError> {
+    let detected_files = detect_files(dir)?;
+    let current_version = match current_version {
+        Some(current_version) => current_version.to_owned(),
+        None => detected_files
+            .first()
+            .map(|file| file.version.to_owned())
+            .ok_or_else(|| {
+                InitError::UndetectedVersion(
+                    "Cannot detect the current version, provide it with `--current-version`"
+                        .to_owned(),
+                )
+            })?,
+    };
+    core::Version::from_str(&current_version)?;
+
+    let (files, skipped_files): (Vec<DetectedFile>, Vec<DetectedFile>) = detected_files
+        .into_iter()
+        .partition(|file| file.version == current_version);
+
+    let mut content = format!(
+        "[semver]\ncurrent_version = {}\ndefault_part = {}\n",
+        toml_string(&current_version),
+        toml_string(&default_part.to_string())
+    );
+    for file in &files {
+        content.push_str(&format!("\n[semver.files.{}]\n", toml_string(&file.path)));
+        if let (Some(search), Some(replace)) = (&file.search, &file.replace) {
+            content.push_str(&format!(
+                "search = {}\nreplace = {}\n",
+                toml_string(search),
+                toml_string(replace)
+            ));
+        }
+        if file.first_only {
+            content.push_str("first_only = true\n");
+        }
+    }
+    if prerelease {
+        content.push_str(&format!("\n[semver.prerelease]\n{}", PRERELEASE_CONFIG));
+    }
+
+    Ok(Scaffold {
+        current_version,
+        files,
+        skipped_files,
+        content,
+    })
+}
+
+/// Writes the scaffolded configuration to `path`, failing if it already exists unless `force`.
+pub fn write(scaffold: &Scaffold, path: &Path, force: bool) -> Result<(), InitError> {
+    if path.exists() && !force {
+        return Err(InitError::AlreadyExists(format!(
+            "File '{}' already exists, use `--force` to overwrite it",
+            path.display()
+        )));
+    }
+    Ok(fs::write(path, &scaffold.content)?)
+}
+
+/// Returns the files of `dir` with a valid version, by precedence.
+fn detect_files(dir: &Path) -> Result<Vec<DetectedFile>, InitError> {
+    let mut detected_files = vec![];
+    for file_name in VERSION_FILES {
+        let content = match fs::read_to_string(dir.join(file_name)) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+        let detected_file = match version_keys(file_name) {
+            Some((format, keys)) => keys.iter().find_map(|key| {
+                let version = structured::get_value(&content, &format, key).ok()?;
+                let raw_line = version_line(&content, "version", &version)?;
+                // Braces are escaped to not be taken as variables
+                let line = raw_line.replace('{', "\\{");
+                Some(DetectedFile {
+                    path: file_name.to_owned(),
+                    search: Some(line.replace(&version, "{current_version}")),
+                    replace: Some(line.replace(&version, "{new_version}")),
+                    first_only: content.matches(&raw_line).count() > 1,
+                    version,
+                })
+            }),
+            None => Some(DetectedFile {
+                path: file_name.to_owned(),
+                version: content.trim().to_owned(),
+                search: None,
+                replace: None,
+                first_only: false,
+            }),
+        };
+        if let Some(detected_file) = detected_file {
+            if core::Version::from_str(&detected_file.version).is_ok() {
+                detected_files.push(detected_file);
+            }
+        }
+    }
+    Ok(detected_files)
+}
+
+/// Returns the format of a version file and the keys where its version can be.
+fn version_keys(file_name: &str) -> Option<(FileFormat, &'static [&'static str])> {
+    match file_name {
+        "Cargo.toml" => Some((FileFormat::Toml, &["package.version"])),
+        "package.json" => Some((FileFormat::Json, &["version"])),
+        "pyproject.toml" => Some((
+            FileFormat::Toml,
+            &["project.version", "tool.poetry.version"],
+        )),
+        _ => None,
+    }
+}
+
+/// Returns the first line with both `name` and `version`, without the surrounding whitespaces and
+/// trailing comma, e.g. `"version": "1.0.0"`.
+fn version_line(content: &str, name: &str, version: &str) -> Option<String> {
+    content
+        .lines()
+        .map(|line| line.trim().trim_end_matches(',').trim_end())
+        .find(|line| line.contains(name) && line.contains(version))
+        .map(|line| line.to_owned())
+}
+
+/// Quotes `value` as a basic TOML string, or as a literal one if it contains quotes or
+/// backslashes, to keep the templates readable.
+fn toml_string(value: &str) -> String {
+    if value.contains(['"', '\\']) && !value.contains(['\'', '\n']) {
+        format!("'{}'", value)
+    } else {
+        toml_edit::Value::from(value).to_string()
+    }
+}
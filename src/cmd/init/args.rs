@@ -0,0 +1,25 @@
+use crate::core;
+use clap::Args;
+
+#[derive(Args)]
+pub struct InitArgs {
+    /// Current version, detected from the well-known files if not provided
+    #[clap(short = 'v', long, display_order = 1)]
+    pub current_version: Option<String>,
+
+    /// Default part to bump
+    #[clap(short = 'p', long, default_value = "minor", display_order = 2)]
+    pub default_part: core::Part,
+
+    /// Omits the prerelease script
+    #[clap(long, display_order = 3)]
+    pub no_prerelease: bool,
+
+    /// Overwrites the configuration file if it already exists
+    #[clap(long, display_order = 4)]
+    pub force: bool,
+
+    /// Prints the configuration without writing it
+    #[clap(long, display_order = 5)]
+    pub dry_run: bool,
+}
@@ -0,0 +1,72 @@
+use super::args::InitArgs;
+use super::{scaffold, write, DetectedFile, CONFIG_FILE_NAME};
+use crate::cmd::output::{self, ErrorCode, OutputFormat};
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(Serialize)]
+struct InitOutput<'a> {
+    path: &'a str,
+    current_version: &'a str,
+    files: Vec<&'a str>,
+    skipped_files: Vec<&'a str>,
+    content: &'a str,
+    dry_run: bool,
+}
+
+fn paths(files: &[DetectedFile]) -> Vec<&str> {
+    files.iter().map(|file| file.path.as_str()).collect()
+}
+
+pub fn init(args: &InitArgs, output: OutputFormat) {
+    let result = scaffold(
+        Path::new("."),
+        args.current_version.as_deref(),
+        &args.default_part,
+        !args.no_prerelease,
+    )
+    .and_then(|scaffold| {
+        if !args.dry_run {
+            write(&scaffold, Path::new(CONFIG_FILE_NAME), args.force)?;
+        }
+        Ok(scaffold)
+    });
+    match result {
+        Ok(scaffold) => {
+            match output {
+                OutputFormat::Text => {
+                    for file in &scaffold.skipped_files {
+                        println!(
+                            "Skipped '{}' whose version '{}' differs from the current one",
+                            file.path, file.version
+                        );
+                    }
+                    if args.dry_run {
+                        print!("{}", scaffold.content);
+                    } else {
+                        println!(
+                            "Created '{}' with version '{}'",
+                            CONFIG_FILE_NAME, scaffold.current_version
+                        );
+                    }
+                }
+                OutputFormat::Json => output::print_json(&InitOutput {
+                    path: CONFIG_FILE_NAME,
+                    current_version: &scaffold.current_version,
+                    files: paths(&scaffold.files),
+                    skipped_files: paths(&scaffold.skipped_files),
+                    content: &scaffold.content,
+                    dry_run: args.dry_run,
+                }),
+            }
+            std::process::exit(0);
+        }
+        Err(err) => {
+            match output {
+                OutputFormat::Text => println!("Error: {}", err),
+                OutputFormat::Json => output::print_json_error(err.code(), err.to_string()),
+            }
+            std::process::exit(1);
+        }
+    }
+}
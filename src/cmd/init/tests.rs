@@ -0,0 +1,193 @@
+use super::{scaffold, write, DetectedFile, InitError};
+use crate::config::Config;
+use crate::core::{CorePart, Part, VersionError};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+fn with_files<F>(test_func_name: &str, files: &[(&str, &str)], test_func: F)
+where
+    F: Fn(&Path),
+{
+    let dir = format!("./__{}", test_func_name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file_path, content) in files {
+        fs::write(format!("{}/{}", dir, file_path), content).unwrap();
+    }
+    test_func(Path::new(&dir));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+fn detected_file(path: &str, version: &str, search: Option<&str>) -> DetectedFile {
+    DetectedFile {
+        path: path.to_owned(),
+        version: version.to_owned(),
+        search: search.map(|search| search.replace("{}", "{current_version}")),
+        replace: search.map(|search| search.replace("{}", "{new_version}")),
+        first_only: false,
+    }
+}
+
+#[test]
+fn test_scaffold() {
+    with_files(
+        "test_init_scaffold",
+        &[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"app\"\nversion = \"1.2.3\"\n",
+            ),
+            (
+                "package.json",
+                "{\n  \"name\": \"app\",\n  \"version\": \"1.2.3\",\n  \"private\": true\n}\n",
+            ),
+            ("pyproject.toml", "[tool.poetry]\nversion = '1.2.3'\n"),
+            ("VERSION", "1.2.3\n"),
+        ],
+        |dir| {
+            let scaffold = scaffold(dir, None, &Part::Core(CorePart::Minor), false).unwrap();
+            assert_eq!(scaffold.current_version, "1.2.3");
+            assert_eq!(
+                scaffold.files,
+                vec![
+                    detected_file("Cargo.toml", "1.2.3", Some("version = \"{}\"")),
+                    detected_file("package.json", "1.2.3", Some("\"version\": \"{}\"")),
+                    detected_file("pyproject.toml", "1.2.3", Some("version = '{}'")),
+                    detected_file("VERSION", "1.2.3", None),
+                ]
+            );
+            assert_eq!(scaffold.skipped_files, vec![]);
+            assert_eq!(
+                scaffold.content,
+                r#"[semver]
+current_version = "1.2.3"
+default_part = "minor"
+
+[semver.files."Cargo.toml"]
+search = 'version = "{current_version}"'
+replace = 'version = "{new_version}"'
+
+[semver.files."package.json"]
+search = '"version": "{current_version}"'
+replace = '"version": "{new_version}"'
+
+[semver.files."pyproject.toml"]
+search = "version = '{current_version}'"
+replace = "version = '{new_version}'"
+
+[semver.files."VERSION"]
+"#
+            );
+        },
+    );
+}
+
+#[test]
+fn test_scaffold_is_valid_config() {
+    with_files(
+        "test_init_scaffold_is_valid_config",
+        &[("package.json", r#"{"name": "app", "version": "0.1.0"}"#)],
+        |dir| {
+            let scaffold = scaffold(dir, None, &Part::Core(CorePart::Patch), true).unwrap();
+            let config = Config::from_str(&scaffold.content).unwrap();
+            assert_eq!(config.current_version, "0.1.0");
+            assert_eq!(config.default_part, Part::Core(CorePart::Patch));
+            assert!(config.bump_prerelease_func.is_some() || config.prerelease_strategy.is_some());
+            // The braces of the one-line object are escaped
+            assert_eq!(
+                config.files["package.json"].search,
+                Some(r#"\{"name": "app", "version": "{current_version}"}"#.to_owned())
+            );
+        },
+    );
+}
+
+#[test]
+fn test_scaffold_current_version() {
+    with_files(
+        "test_init_scaffold_current_version",
+        &[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"app\"\nversion = \"1.0.0\"\n",
+            ),
+            ("VERSION", "2.0.0"),
+        ],
+        |dir| {
+            let scaffold =
+                scaffold(dir, Some("2.0.0"), &Part::Core(CorePart::Minor), false).unwrap();
+            assert_eq!(scaffold.current_version, "2.0.0");
+            assert_eq!(
+                scaffold.files,
+                vec![detected_file("VERSION", "2.0.0", None)]
+            );
+            assert_eq!(
+                scaffold.skipped_files,
+                vec![detected_file(
+                    "Cargo.toml",
+                    "1.0.0",
+                    Some("version = \"{}\"")
+                )]
+            );
+        },
+    );
+}
+
+#[test]
+fn test_scaffold_first_only() {
+    with_files(
+        "test_init_scaffold_first_only",
+        &[(
+            "Cargo.toml",
+            "[package]\nname = \"app\"\nversion = \"1.0.0\"\n\n[dependencies]\nlib = { version = \"1.0.0\" }\n",
+        )],
+        |dir| {
+            let scaffold = scaffold(dir, None, &Part::Core(CorePart::Minor), false).unwrap();
+            assert!(scaffold.files[0].first_only);
+            assert!(scaffold.content.ends_with(
+                "replace = 'version = \"{new_version}\"'\nfirst_only = true\n"
+            ));
+        },
+    );
+}
+
+#[test]
+fn test_scaffold_error() {
+    with_files(
+        "test_init_scaffold_error",
+        &[("VERSION", "latest")],
+        |dir| {
+            assert_eq!(
+                scaffold(dir, None, &Part::Core(CorePart::Minor), false),
+                Err(InitError::UndetectedVersion(
+                    "Cannot detect the current version, provide it with `--current-version`"
+                        .to_owned()
+                ))
+            );
+            assert_eq!(
+                scaffold(dir, Some("1.0"), &Part::Core(CorePart::Minor), false),
+                Err(InitError::InvalidVersion(VersionError::InvalidVersion(
+                    "1.0".to_owned()
+                )))
+            );
+        },
+    );
+}
+
+#[test]
+fn test_write() {
+    with_files("test_init_write", &[("VERSION", "1.0.0")], |dir| {
+        let scaffold = scaffold(dir, None, &Part::Core(CorePart::Minor), false).unwrap();
+        let path = dir.join(".semver.toml");
+        assert!(write(&scaffold, &path, false).is_ok());
+        assert_eq!(fs::read_to_string(&path).unwrap(), scaffold.content);
+        assert_eq!(
+            write(&scaffold, &path, false),
+            Err(InitError::AlreadyExists(format!(
+                "File '{}' already exists, use `--force` to overwrite it",
+                path.display()
+            )))
+        );
+        assert!(write(&scaffold, &path, true).is_ok());
+    });
+}
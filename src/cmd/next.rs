@@ -0,0 +1,5 @@
+mod args;
+#[cfg(test)]
+mod tests;
+pub use args::NextArgs;
+pub mod cli;
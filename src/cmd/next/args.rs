@@ -0,0 +1,35 @@
+use crate::cmd::bump::{FinalizedVersionArgs, VersionArgs};
+use crate::cmd::error::ArgumentsError;
+use crate::cmd::helpers;
+use crate::config;
+use clap::Args;
+
+#[derive(Args)]
+pub struct NextArgs {
+    #[clap(flatten)]
+    version: VersionArgs,
+}
+
+impl helpers::FinalizeArgs for NextArgs {
+    type FinalizedArgs = FinalizedVersionArgs;
+
+    fn get_config(&self) -> Option<String> {
+        self.version.get_config()
+    }
+
+    fn get_required_args(&self) -> Vec<String> {
+        self.version.get_required_args()
+    }
+
+    fn validate_args(&self, args: &Self::FinalizedArgs) -> Result<(), ArgumentsError> {
+        self.version.validate(args)
+    }
+
+    fn finalize_from_config(&self, config: config::Config) -> Self::FinalizedArgs {
+        self.version.finalize_from_config(&config)
+    }
+
+    fn finalize_from_self(&self) -> Option<Self::FinalizedArgs> {
+        self.version.finalize_from_self()
+    }
+}
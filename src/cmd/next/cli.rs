@@ -0,0 +1,36 @@
+use super::args::NextArgs;
+use crate::cmd::bump::next_version;
+use crate::cmd::error;
+use crate::cmd::helpers::FinalizeArgs;
+use crate::cmd::output::{self, ErrorCode, OutputFormat, VersionOutput};
+
+pub fn next(args: &NextArgs, output: OutputFormat) {
+    match args.finalize() {
+        Ok(args) => match next_version(
+            &args.current_version,
+            args.last_stable_version.as_deref(),
+            &args.part,
+            args.new_prerelease,
+            args.finalize_prerelease,
+            args.bump_prerelease_func,
+            args.bump_buildmetadata_func,
+            args.buildmetadata_policy,
+        ) {
+            Ok(version) => {
+                match output {
+                    OutputFormat::Text => println!("{}", version),
+                    OutputFormat::Json => output::print_json(&VersionOutput::from(&version)),
+                }
+                std::process::exit(0);
+            }
+            Err(err) => {
+                match output {
+                    OutputFormat::Text => println!("Error: {}", err),
+                    OutputFormat::Json => output::print_json_error(err.code(), err.to_string()),
+                }
+                std::process::exit(1);
+            }
+        },
+        Err(err) => error::handle_args_error(err, output),
+    }
+}
@@ -0,0 +1,100 @@
+use crate::cmd::bump::next_version;
+use crate::cmd::error::ArgumentsError;
+use crate::cmd::helpers::FinalizeArgs;
+use crate::cmd::{Cli, Commands};
+use crate::core::{BumpError, Version, VersionError};
+use clap::Parser;
+
+fn next_from_args(args: &[&str]) -> Result<Version, VersionError> {
+    let cli = Cli::try_parse_from([&["semver", "next"], args].concat()).unwrap();
+    match cli.command {
+        Commands::Next(args) => {
+            let args = args.finalize().unwrap();
+            next_version(
+                &args.current_version,
+                args.last_stable_version.as_deref(),
+                &args.part,
+                args.new_prerelease,
+                args.finalize_prerelease,
+                args.bump_prerelease_func,
+                args.bump_buildmetadata_func,
+                args.buildmetadata_policy,
+            )
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_next() {
+    let test_cases = vec![
+        (vec!["-v", "1.2.3", "-p", "major"], "2.0.0"),
+        (vec!["-v", "1.2.3", "-p", "minor"], "1.3.0"),
+        (vec!["-v", "1.2.3", "-p", "patch"], "1.2.4"),
+        (
+            vec!["-v", "1.2.3-dev.1", "-p", "patch", "--finalize-prerelease"],
+            "1.2.3",
+        ),
+        (
+            vec![
+                "-v",
+                "1.2.3",
+                "-p",
+                "buildmetadata",
+                "--buildmetadata",
+                "sha.abc",
+            ],
+            "1.2.3+sha.abc",
+        ),
+        (vec!["-v", "1.2.3+sha.abc", "-p", "minor"], "1.3.0"),
+        (
+            vec!["-v", "1.2.3+sha.abc", "-p", "minor", "--keep-buildmetadata"],
+            "1.3.0+sha.abc",
+        ),
+    ];
+    for tc in test_cases {
+        assert_eq!(next_from_args(&tc.0).unwrap().to_string(), tc.1);
+    }
+}
+
+#[test]
+fn test_next_error() {
+    assert_eq!(
+        next_from_args(&["-v", "1.2.3", "-p", "minor", "--new-prerelease"]),
+        Err(VersionError::BumpError(BumpError::MissingBumpScript))
+    );
+}
+
+#[test]
+fn test_next_invalid_buildmetadata() {
+    let test_cases = vec![
+        (
+            vec!["-v", "1.2.3", "-p", "buildmetadata"],
+            "Missing build metadata, it must be provided with `--buildmetadata` to bump the \"buildmetadata\" part",
+        ),
+        (
+            vec!["-v", "1.2.3", "-p", "patch", "--buildmetadata", "sha.abc"],
+            "`--buildmetadata` can only be provided to bump the \"buildmetadata\" part",
+        ),
+    ];
+    for tc in test_cases {
+        let cli = Cli::try_parse_from([&["semver", "next"], &tc.0[..]].concat()).unwrap();
+        match cli.command {
+            Commands::Next(args) => assert!(matches!(
+                args.finalize(),
+                Err(ArgumentsError::InvalidArguments(msg)) if msg == tc.1
+            )),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn test_next_missing_args() {
+    let cli = Cli::try_parse_from(["semver", "next", "-v", "1.2.3"]).unwrap();
+    match cli.command {
+        // Without a configuration file `finalize` would fall back to the discovered one
+        Commands::Next(args) => assert!(args.finalize_from_self().is_none()),
+        _ => unreachable!(),
+    }
+}
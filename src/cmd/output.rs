@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod tests;
+use crate::cmd::bump::GenericBumpError;
+use crate::cmd::error::ArgumentsError;
+use crate::cmd::get::GetError;
+use crate::cmd::init::InitError;
+use crate::config::ConfigError;
+use crate::core::{BumpError, Version, VersionError};
+use crate::file::FileBumpError;
+use crate::git::GitError;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, clap::ArgEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// A version as both its string representation and its parts.
+#[derive(Serialize)]
+pub struct VersionOutput<'a> {
+    pub version: String,
+    #[serde(flatten)]
+    pub parts: &'a Version,
+}
+
+impl<'a> From<&'a Version> for VersionOutput<'a> {
+    fn from(version: &'a Version) -> Self {
+        VersionOutput {
+            version: version.to_string(),
+            parts: version,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorOutput<'a> {
+    error: ErrorDetails<'a>,
+}
+
+#[derive(Serialize)]
+struct ErrorDetails<'a> {
+    code: &'a str,
+    message: String,
+}
+
+/// Stable identifier of an error to be used in the JSON output.
+pub trait ErrorCode {
+    fn code(&self) -> &'static str;
+}
+
+impl ErrorCode for VersionError {
+    fn code(&self) -> &'static str {
+        match self {
+            VersionError::InvalidVersion(_) => "invalid_version",
+            VersionError::UnexpectedError(_) => "unexpected_error",
+            VersionError::UnsupportedVersion(_) => "unsupported_version",
+            VersionError::InvalidRequirement(_) => "invalid_requirement",
+            VersionError::ParsingError(_) => "parsing_error",
+            VersionError::BumpError(err) => err.code(),
+        }
+    }
+}
+
+impl ErrorCode for BumpError {
+    fn code(&self) -> &'static str {
+        match self {
+            BumpError::AnyError(_) => "bump_script_error",
+            BumpError::MissingBumpScript => "missing_bump_script",
+            BumpError::MissingBuildMetadata => "missing_buildmetadata",
+            BumpError::InvalidOperation(_) => "invalid_operation",
+            BumpError::CommandError(_) => "bump_command_error",
+        }
+    }
+}
+
+impl ErrorCode for FileBumpError {
+    fn code(&self) -> &'static str {
+        match self {
+            FileBumpError::Io(_) => "file_io_error",
+            FileBumpError::NoOp(_) => "file_no_op",
+            FileBumpError::InvalidTemplate(_) => "file_invalid_template",
+            FileBumpError::UnexpectedMatches(_) => "file_unexpected_matches",
+            FileBumpError::InvalidContent(_) => "file_invalid_content",
+        }
+    }
+}
+
+impl ErrorCode for GenericBumpError {
+    fn code(&self) -> &'static str {
+        match self {
+            GenericBumpError::Version(err) => err.code(),
+            GenericBumpError::File(err) => err.code(),
+        }
+    }
+}
+
+impl ErrorCode for GetError {
+    fn code(&self) -> &'static str {
+        match self {
+            GetError::Version(err) => err.code(),
+            GetError::Template(_) => "invalid_format",
+        }
+    }
+}
+
+impl ErrorCode for InitError {
+    fn code(&self) -> &'static str {
+        match self {
+            InitError::Io(_) => "init_io_error",
+            InitError::AlreadyExists(_) => "init_already_exists",
+            InitError::UndetectedVersion(_) => "init_undetected_version",
+            InitError::InvalidVersion(err) => err.code(),
+        }
+    }
+}
+
+impl ErrorCode for GitError {
+    fn code(&self) -> &'static str {
+        match self {
+            GitError::Io(_) => "git_io_error",
+            GitError::InvalidTemplate(_) => "git_invalid_template",
+            GitError::InvalidState(_) => "git_invalid_state",
+            GitError::CommandFailed(_) => "git_command_failed",
+        }
+    }
+}
+
+impl ErrorCode for ConfigError {
+    fn code(&self) -> &'static str {
+        match self {
+            ConfigError::ParseError(_) | ConfigError::JsonParseError(_) => "config_parse_error",
+            ConfigError::MissingSection(_) => "config_missing_section",
+            ConfigError::IOError(_) => "config_io_error",
+            ConfigError::InvalidPart(..) => "config_invalid_part",
+            ConfigError::InvalidVersion(..) => "config_invalid_version",
+            ConfigError::UnorderedVersions(..) => "config_unordered_versions",
+            ConfigError::MissingFile(..) => "config_missing_file",
+            ConfigError::InvalidTemplate(..) => "config_invalid_template",
+            ConfigError::InvalidBumpScript(..) => "config_invalid_bump_script",
+            ConfigError::InvalidPrerelease(..) => "config_invalid_prerelease",
+            ConfigError::InvalidLines(..) => "config_invalid_lines",
+            ConfigError::UnsupportedBumpScript(_) => "config_unsupported_bump_script",
+        }
+    }
+}
+
+impl ErrorCode for ArgumentsError {
+    fn code(&self) -> &'static str {
+        match self {
+            ArgumentsError::ConfigError(err) => err.code(),
+            ArgumentsError::MissingArguments(_) => "missing_arguments",
+            ArgumentsError::InvalidArguments(_) => "invalid_arguments",
+        }
+    }
+}
+
+pub fn print_json<T: Serialize>(value: &T) {
+    println!("{}", serde_json::to_string_pretty(value).unwrap());
+}
+
+pub fn print_json_error(code: &str, message: String) {
+    print_json(&ErrorOutput {
+        error: ErrorDetails { code, message },
+    });
+}
@@ -0,0 +1,73 @@
+use super::{ErrorCode, VersionOutput};
+use crate::cmd::bump::GenericBumpError;
+use crate::core::{BumpError, Version, VersionError};
+use crate::file::FileBumpError;
+use crate::tests::v4;
+
+#[test]
+fn test_version_output() {
+    let v4 = v4();
+    assert_eq!(
+        serde_json::to_value(VersionOutput::from(&v4)).unwrap(),
+        serde_json::json!({
+            "version": "1.2.3-dev.5+build.1",
+            "major": 1,
+            "minor": 2,
+            "patch": 3,
+            "prerelease": "dev.5",
+            "buildmetadata": "build.1",
+        })
+    );
+
+    let v = Version::with_values(1, 0, 0, None);
+    assert_eq!(
+        serde_json::to_value(VersionOutput::from(&v)).unwrap(),
+        serde_json::json!({
+            "version": "1.0.0",
+            "major": 1,
+            "minor": 0,
+            "patch": 0,
+            "prerelease": null,
+            "buildmetadata": null,
+        })
+    );
+}
+
+#[test]
+fn test_error_codes() {
+    let test_cases: Vec<(Box<dyn ErrorCode>, &str)> = vec![
+        (
+            Box::new(VersionError::InvalidVersion("1.0".to_owned())),
+            "invalid_version",
+        ),
+        (
+            Box::new(VersionError::InvalidRequirement("^a".to_owned())),
+            "invalid_requirement",
+        ),
+        (
+            Box::new(BumpError::MissingBumpScript),
+            "missing_bump_script",
+        ),
+        (
+            Box::new(BumpError::MissingBuildMetadata),
+            "missing_buildmetadata",
+        ),
+        (
+            Box::new(GenericBumpError::Version(VersionError::BumpError(
+                BumpError::InvalidOperation("".to_owned()),
+            ))),
+            "invalid_operation",
+        ),
+        (
+            Box::new(BumpError::CommandError("".to_owned())),
+            "bump_command_error",
+        ),
+        (
+            Box::new(GenericBumpError::File(FileBumpError::NoOp("".to_owned()))),
+            "file_no_op",
+        ),
+    ];
+    for tc in test_cases {
+        assert_eq!(tc.0.code(), tc.1);
+    }
+}
@@ -0,0 +1,18 @@
+mod args;
+#[cfg(test)]
+mod tests;
+use crate::cmd::validate;
+use crate::core;
+pub use args::SatisfiesArgs;
+use std::str::FromStr;
+pub mod cli;
+
+pub fn satisfies(version: &str, requirement: &str) -> Result<bool, core::VersionError> {
+    if !validate::validate(version) {
+        Err(core::VersionError::InvalidVersion(version.to_owned()))
+    } else {
+        let v = core::Version::from_str(version)?;
+        let req = core::Requirement::from_str(requirement)?;
+        Ok(req.matches(&v))
+    }
+}
@@ -0,0 +1,10 @@
+use clap::Args;
+
+#[derive(Args)]
+pub struct SatisfiesArgs {
+    /// Version to check
+    pub version: String,
+
+    /// Requirement that the version must satisfy (e.g. `^1.2`, `~1.2.3`, `>=1.0.0 <2.0.0`)
+    pub requirement: String,
+}
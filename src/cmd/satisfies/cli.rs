@@ -0,0 +1,41 @@
+use super::args::SatisfiesArgs;
+use super::satisfies as do_satisfies;
+use crate::cmd::output::{self, ErrorCode, OutputFormat};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct SatisfiesOutput<'a> {
+    version: &'a str,
+    requirement: &'a str,
+    satisfies: bool,
+}
+
+pub fn satisfies(args: &SatisfiesArgs, output: OutputFormat) {
+    let version = &args.version;
+    let requirement = &args.requirement;
+    match do_satisfies(version, requirement) {
+        Ok(satisfies) => {
+            match output {
+                OutputFormat::Text if satisfies => {
+                    println!("Version '{}' satisfies '{}'!", version, requirement)
+                }
+                OutputFormat::Text => {
+                    println!("Version '{}' does not satisfy '{}'!", version, requirement)
+                }
+                OutputFormat::Json => output::print_json(&SatisfiesOutput {
+                    version,
+                    requirement,
+                    satisfies,
+                }),
+            }
+            std::process::exit(if satisfies { 0 } else { 1 });
+        }
+        Err(err) => {
+            match output {
+                OutputFormat::Text => println!("Error: {}", err),
+                OutputFormat::Json => output::print_json_error(err.code(), err.to_string()),
+            }
+            std::process::exit(2);
+        }
+    }
+}
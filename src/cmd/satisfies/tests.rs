@@ -0,0 +1,30 @@
+use super::satisfies;
+use crate::core::VersionError;
+
+#[test]
+fn test_satisfies() {
+    let test_cases = vec![
+        ("1.2.3", "^1.2", true),
+        ("2.0.0", "^1.2", false),
+        ("1.2.9", "~1.2.3", true),
+        ("1.3.0", "~1.2.3", false),
+        ("1.5.0", ">=1.0.0 <2.0.0", true),
+        ("2.0.0-rc.1", ">=1.0.0 <2.0.0", false),
+        ("3.1.0", "^1.2 || ^3", true),
+    ];
+    for tc in test_cases {
+        assert_eq!(satisfies(tc.0, tc.1), Ok(tc.2));
+    }
+}
+
+#[test]
+fn test_satisfies_invalid() {
+    assert_eq!(
+        satisfies("1.2", "^1.2"),
+        Err(VersionError::InvalidVersion("1.2".to_owned()))
+    );
+    assert!(matches!(
+        satisfies("1.2.3", "^abc"),
+        Err(VersionError::InvalidRequirement(_))
+    ));
+}
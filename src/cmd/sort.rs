@@ -0,0 +1,33 @@
+mod args;
+#[cfg(test)]
+mod tests;
+use crate::cmd::validate;
+use crate::core;
+pub use args::{MaxArgs, SortArgs};
+use std::str::FromStr;
+pub mod cli;
+
+/// Sorts the versions by precedence, from the lowest to the greatest, keeping only the stable
+/// ones if `stable_only` is set and the ones satisfying `requirement` if any.
+pub fn sort(
+    versions: &[String],
+    stable_only: bool,
+    requirement: Option<&str>,
+) -> Result<Vec<core::Version>, core::VersionError> {
+    let req = requirement.map(core::Requirement::from_str).transpose()?;
+    let mut parsed_versions = vec![];
+    for version in versions {
+        if !validate::validate(version) {
+            return Err(core::VersionError::InvalidVersion(version.to_owned()));
+        }
+        let v = core::Version::from_str(version)?;
+        if stable_only && !v.is_stable() {
+            continue;
+        }
+        if req.as_ref().is_none_or(|req| req.matches(&v)) {
+            parsed_versions.push(v);
+        }
+    }
+    parsed_versions.sort();
+    Ok(parsed_versions)
+}
@@ -0,0 +1,41 @@
+use clap::Args;
+
+#[derive(Args)]
+pub struct SortArgs {
+    /// Versions to sort
+    ///
+    /// If not provided, they're read from the standard input one per line.
+    pub versions: Vec<String>,
+
+    /// Excludes the prereleases
+    #[clap(long, display_order = 1)]
+    pub stable_only: bool,
+
+    /// Keeps only the versions satisfying the requirement
+    #[clap(short, long, display_order = 2)]
+    pub requirement: Option<String>,
+
+    /// Sorts from the greatest to the lowest
+    #[clap(long, display_order = 3)]
+    pub reverse: bool,
+}
+
+#[derive(Args)]
+pub struct MaxArgs {
+    /// Versions among which to find the greatest
+    ///
+    /// If not provided, they're read from the standard input one per line.
+    pub versions: Vec<String>,
+
+    /// Excludes the prereleases
+    #[clap(long, display_order = 1)]
+    pub stable_only: bool,
+
+    /// Considers only the versions satisfying the requirement
+    #[clap(short, long, display_order = 2)]
+    pub requirement: Option<String>,
+
+    /// Finds the lowest version instead
+    #[clap(long, display_order = 3)]
+    pub min: bool,
+}
@@ -0,0 +1,98 @@
+use super::args::{MaxArgs, SortArgs};
+use super::sort as do_sort;
+use crate::cmd::output::{self, ErrorCode, OutputFormat, VersionOutput};
+use crate::core;
+use serde::Serialize;
+use std::io::{self, BufRead};
+
+#[derive(Serialize)]
+struct SortOutput<'a> {
+    versions: Vec<VersionOutput<'a>>,
+}
+
+pub fn sort(args: &SortArgs, output: OutputFormat) {
+    let mut versions = sort_versions(
+        &args.versions,
+        args.stable_only,
+        args.requirement.as_deref(),
+        output,
+    );
+    if args.reverse {
+        versions.reverse();
+    }
+    match output {
+        OutputFormat::Text => {
+            for version in versions {
+                println!("{}", version);
+            }
+        }
+        OutputFormat::Json => output::print_json(&SortOutput {
+            versions: versions.iter().map(VersionOutput::from).collect(),
+        }),
+    }
+    std::process::exit(0);
+}
+
+pub fn max(args: &MaxArgs, output: OutputFormat) {
+    let versions = sort_versions(
+        &args.versions,
+        args.stable_only,
+        args.requirement.as_deref(),
+        output,
+    );
+    let found = if args.min {
+        versions.first()
+    } else {
+        versions.last()
+    };
+    match found {
+        Some(version) => {
+            match output {
+                OutputFormat::Text => println!("{}", version),
+                OutputFormat::Json => output::print_json(&VersionOutput::from(version)),
+            }
+            std::process::exit(0);
+        }
+        None => exit_with_error("no_versions_found", "no versions found".to_owned(), output),
+    }
+}
+
+fn sort_versions(
+    versions: &[String],
+    stable_only: bool,
+    requirement: Option<&str>,
+    output: OutputFormat,
+) -> Vec<core::Version> {
+    let versions = if versions.is_empty() {
+        match read_stdin_versions() {
+            Ok(versions) => versions,
+            Err(err) => exit_with_error("stdin_io_error", err.to_string(), output),
+        }
+    } else {
+        versions.to_vec()
+    };
+    match do_sort(&versions, stable_only, requirement) {
+        Ok(versions) => versions,
+        Err(err) => exit_with_error(err.code(), err.to_string(), output),
+    }
+}
+
+fn exit_with_error(code: &str, message: String, output: OutputFormat) -> ! {
+    match output {
+        OutputFormat::Text => println!("Error: {}", message),
+        OutputFormat::Json => output::print_json_error(code, message),
+    }
+    std::process::exit(1);
+}
+
+fn read_stdin_versions() -> Result<Vec<String>, io::Error> {
+    let mut versions = vec![];
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        let version = line.trim();
+        if !version.is_empty() {
+            versions.push(version.to_owned());
+        }
+    }
+    Ok(versions)
+}
@@ -0,0 +1,58 @@
+use super::sort;
+use crate::core::VersionError;
+
+fn to_strings(versions: &[&str]) -> Vec<String> {
+    versions.iter().map(|v| v.to_string()).collect()
+}
+
+fn sorted(versions: &[&str], stable_only: bool, requirement: Option<&str>) -> Vec<String> {
+    sort(&to_strings(versions), stable_only, requirement)
+        .unwrap()
+        .iter()
+        .map(|v| v.to_string())
+        .collect()
+}
+
+#[test]
+fn test_sort() {
+    let versions = [
+        "1.0.0",
+        "1.0.0-rc.1",
+        "0.10.0",
+        "0.9.0",
+        "1.0.0-beta.11",
+        "1.0.0-beta.2",
+    ];
+    assert_eq!(
+        sorted(&versions, false, None),
+        vec![
+            "0.9.0",
+            "0.10.0",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0"
+        ]
+    );
+    assert_eq!(
+        sorted(&versions, true, None),
+        vec!["0.9.0", "0.10.0", "1.0.0"]
+    );
+    assert_eq!(
+        sorted(&versions, false, Some("^0.9 || >=1.0.0-beta.5 <1.0.0")),
+        vec!["0.9.0", "1.0.0-beta.11", "1.0.0-rc.1"]
+    );
+    assert_eq!(sorted(&[], false, None), Vec::<String>::new());
+}
+
+#[test]
+fn test_sort_invalid() {
+    assert_eq!(
+        sort(&to_strings(&["1.0.0", "v1.0.0"]), false, None),
+        Err(VersionError::InvalidVersion("v1.0.0".to_owned()))
+    );
+    assert!(matches!(
+        sort(&to_strings(&["1.0.0"]), false, Some("abc")),
+        Err(VersionError::InvalidRequirement(_))
+    ));
+}
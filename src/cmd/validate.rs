@@ -12,8 +12,6 @@ const PATTERN_MAJOR: &str = r"^(?P<major>0|[1-9]\d*)$";
 const PATTERN_MINOR: &str = r"^(?P<minor>0|[1-9]\d*)$";
 const PATTERN_PATCH: &str = r"^(?P<patch>0|[1-9]\d*)$";
 const PATTERN_PRERELEASE: &str = r"^(?P<prerelease>(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)(?:\.(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*)$";
-
-#[allow(dead_code)]
 const PATTERN_BUILDMETADATA: &str = r"^(?P<buildmetadata>[0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*)$";
 
 pub fn validate(version: &str) -> bool {
@@ -26,7 +24,7 @@ pub fn validate_part(s: &str, part: Option<&Part>) -> bool {
         Some(Part::Core(CorePart::Minor)) => PATTERN_MINOR,
         Some(Part::Core(CorePart::Patch)) => PATTERN_PATCH,
         Some(Part::Extension(ExtensionPart::Prerelease)) => PATTERN_PRERELEASE,
-        // TODO: No support for `buildmetadata` part yet
+        Some(Part::Extension(ExtensionPart::BuildMetadata)) => PATTERN_BUILDMETADATA,
         _ => PATTERN,
     };
     let re = Regex::new(pattern).unwrap();
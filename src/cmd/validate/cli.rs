@@ -1,16 +1,21 @@
 use super::args::ValidateArgs;
 use super::validate as do_validate;
+use crate::cmd::output::{self, OutputFormat};
+use serde::Serialize;
 
-pub fn validate(args: &ValidateArgs) {
+#[derive(Serialize)]
+struct ValidateOutput<'a> {
+    version: &'a str,
+    valid: bool,
+}
+
+pub fn validate(args: &ValidateArgs, output: OutputFormat) {
     let version = &args.version;
-    match do_validate(version) {
-        true => {
-            println!("Version '{}' is valid!", version);
-            std::process::exit(0);
-        }
-        false => {
-            println!("Version '{}' is not valid!", version);
-            std::process::exit(1);
-        }
+    let valid = do_validate(version);
+    match output {
+        OutputFormat::Text if valid => println!("Version '{}' is valid!", version),
+        OutputFormat::Text => println!("Version '{}' is not valid!", version),
+        OutputFormat::Json => output::print_json(&ValidateOutput { version, valid }),
     }
+    std::process::exit(if valid { 0 } else { 1 });
 }
@@ -117,6 +117,9 @@ fn test_correct_parts() {
             "---whatever-abc-def.1.ghi---",
             Part::Extension(ExtensionPart::Prerelease),
         ),
+        ("build.1", Part::Extension(ExtensionPart::BuildMetadata)),
+        ("sha.0abc123", Part::Extension(ExtensionPart::BuildMetadata)),
+        ("001", Part::Extension(ExtensionPart::BuildMetadata)),
     ];
     for tc in test_cases {
         assert!(validate_part(tc.0, Some(&tc.1)));
@@ -143,6 +146,9 @@ fn test_wrong_parts() {
         ("rc_1", Part::Extension(ExtensionPart::Prerelease)),
         ("_alpha_", Part::Extension(ExtensionPart::Prerelease)),
         (".dev.", Part::Extension(ExtensionPart::Prerelease)),
+        ("build..1", Part::Extension(ExtensionPart::BuildMetadata)),
+        ("sha_abc", Part::Extension(ExtensionPart::BuildMetadata)),
+        ("+build", Part::Extension(ExtensionPart::BuildMetadata)),
     ];
     for tc in test_cases {
         assert!(!validate_part(tc.0, Some(&tc.1)));
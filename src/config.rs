@@ -1,9 +1,16 @@
 #[cfg(test)]
 mod tests;
-use crate::core;
+use crate::cmd::validate;
+use crate::file::structured;
+use crate::{core, file, git, template};
+#[cfg(feature = "js")]
+use js_sandbox::Script;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 use std::{fmt, fs, io, str};
 use toml;
 
@@ -19,6 +26,29 @@ struct RawConfig {
     default_part: String,
     files: Option<HashMap<String, FileConfig>>,
     prerelease: Option<PrereleaseConfig>,
+    git: Option<GitConfig>,
+}
+
+/// Format of a file whose version is stored under a key rather than found through `search`.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+/// Predefined configuration for the version file of an ecosystem, including its companion files.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Preset {
+    Cargo,
+    Npm,
+    Pyproject,
+    #[serde(rename = "setup.cfg")]
+    SetupCfg,
+    HelmChart,
+    MavenPom,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
@@ -26,6 +56,20 @@ pub struct FileConfig {
     pub search: Option<String>,
     pub replace: Option<String>,
     pub stable_only: Option<bool>,
+    pub search_regex: Option<bool>,
+    /// Fails the bump if `search` is found more than this number of times.
+    pub max_replacements: Option<usize>,
+    /// Fails the bump if `search` is not found exactly this number of times.
+    pub expected_count: Option<usize>,
+    /// Replaces only the first occurrence of `search`.
+    pub first_only: Option<bool>,
+    /// Restricts the search to the lines in this range, 1-based and inclusive.
+    pub lines: Option<(usize, usize)>,
+    pub format: Option<FileFormat>,
+    /// Dotted path of the version in a file with a `format`, e.g. `package.version`.
+    pub key: Option<String>,
+    /// Fills the options not explicitly set according to the preset.
+    pub preset: Option<Preset>,
 }
 
 impl Default for FileConfig {
@@ -40,14 +84,21 @@ impl FileConfig {
             search: None,
             replace: None,
             stable_only: None,
+            search_regex: None,
+            max_replacements: None,
+            expected_count: None,
+            first_only: None,
+            lines: None,
+            format: None,
+            key: None,
+            preset: None,
         }
     }
 
     pub fn with_stable_only() -> Self {
         FileConfig {
-            search: None,
-            replace: None,
             stable_only: Some(true),
+            ..FileConfig::new()
         }
     }
 
@@ -55,7 +106,31 @@ impl FileConfig {
         FileConfig {
             search: Some(search),
             replace: Some(replace),
-            stable_only: None,
+            ..FileConfig::new()
+        }
+    }
+
+    pub fn with_regex(search: String, replace: String) -> Self {
+        FileConfig {
+            search: Some(search),
+            replace: Some(replace),
+            search_regex: Some(true),
+            ..FileConfig::new()
+        }
+    }
+
+    pub fn with_key(format: FileFormat, key: String) -> Self {
+        FileConfig {
+            format: Some(format),
+            key: Some(key),
+            ..FileConfig::new()
+        }
+    }
+
+    pub fn with_preset(preset: Preset) -> Self {
+        FileConfig {
+            preset: Some(preset),
+            ..FileConfig::new()
         }
     }
 
@@ -64,13 +139,221 @@ impl FileConfig {
             search: Some(search),
             replace: Some(replace),
             stable_only: Some(stable_only),
+            ..FileConfig::new()
         }
     }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct PrereleaseConfig {
-    bump_script: String,
+    bump_script: Option<String>,
+    /// Program, followed by its arguments, printing the prerelease given the current version as
+    /// JSON on its standard input.
+    bump_command: Option<Vec<String>>,
+    /// Seconds after which the `bump_command` is killed.
+    bump_command_timeout: Option<u64>,
+    strategy: Option<PrereleaseStrategyKind>,
+    /// Prefix of the prerelease for the `numeric` and `timestamp` strategies.
+    prefix: Option<String>,
+    /// Progression of the prerelease for the `channel` strategy.
+    channels: Option<Vec<String>>,
+}
+
+/// External command bumping the prerelease.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BumpCommand {
+    /// The program followed by its arguments.
+    pub args: Vec<String>,
+    pub timeout: Duration,
+}
+
+const DEFAULT_BUMP_COMMAND_TIMEOUT: u64 = 10;
+
+/// Built-in strategy to bump the prerelease instead of a `bump_script`.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PrereleaseStrategyKind {
+    Numeric,
+    Timestamp,
+    Channel,
+}
+
+impl PrereleaseConfig {
+    fn command(&self) -> Option<BumpCommand> {
+        self.bump_command.to_owned().map(|args| BumpCommand {
+            args,
+            timeout: Duration::from_secs(
+                self.bump_command_timeout
+                    .unwrap_or(DEFAULT_BUMP_COMMAND_TIMEOUT),
+            ),
+        })
+    }
+
+    fn strategy(&self) -> Option<core::PrereleaseStrategy> {
+        let prefix = || self.prefix.to_owned().unwrap_or_default();
+        self.strategy.map(|kind| match kind {
+            PrereleaseStrategyKind::Numeric => core::PrereleaseStrategy::Numeric(prefix()),
+            PrereleaseStrategyKind::Timestamp => core::PrereleaseStrategy::Timestamp(prefix()),
+            PrereleaseStrategyKind::Channel => {
+                core::PrereleaseStrategy::Channel(self.channels.to_owned().unwrap_or_else(|| {
+                    core::DEFAULT_CHANNELS
+                        .iter()
+                        .map(|channel| channel.to_string())
+                        .collect()
+                }))
+            }
+        })
+    }
+}
+
+/// Committing and tagging with git after a successful bump.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GitConfig {
+    commit: Option<bool>,
+    /// Creates an annotated tag, which implies `commit`.
+    tag: Option<bool>,
+    /// Template of the message of both the commit and the tag.
+    message: Option<String>,
+    /// Template of the name of the tag.
+    tag_name: Option<String>,
+}
+
+/// What to do with git after a successful bump, with the templates resolved to their defaults.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GitOptions {
+    pub commit: bool,
+    pub tag: bool,
+    pub message: String,
+    pub tag_name: String,
+}
+
+pub const DEFAULT_COMMIT_MESSAGE: &str = "Bump version: {current_version} → {new_version}";
+pub const DEFAULT_TAG_NAME: &str = "v{new_version}";
+
+impl Default for GitOptions {
+    fn default() -> Self {
+        GitOptions {
+            commit: false,
+            tag: false,
+            message: DEFAULT_COMMIT_MESSAGE.to_owned(),
+            tag_name: DEFAULT_TAG_NAME.to_owned(),
+        }
+    }
+}
+
+impl From<&GitConfig> for GitOptions {
+    fn from(git: &GitConfig) -> Self {
+        let default = GitOptions::default();
+        GitOptions {
+            commit: git.commit.unwrap_or(default.commit),
+            tag: git.tag.unwrap_or(default.tag),
+            message: git.message.to_owned().unwrap_or(default.message),
+            tag_name: git.tag_name.to_owned().unwrap_or(default.tag_name),
+        }
+    }
+}
+
+const PRERELEASE: core::Part = core::Part::Extension(core::ExtensionPart::Prerelease);
+
+/// Environment variable with the path of the configuration file, used when not provided.
+pub const CONFIG_ENV_VAR: &str = "SEMVER_CONFIG";
+
+/// Names of the files that can host the configuration, by precedence.
+const CONFIG_FILE_NAMES: [&str; 5] = [
+    ".semver.toml",
+    "semver.toml",
+    "Cargo.toml",
+    "pyproject.toml",
+    "package.json",
+];
+
+const VCS_DIRS: [&str; 3] = [".git", ".hg", ".svn"];
+
+/// Looks for a configuration file in `dir` and its parents up to the root of the repository,
+/// returning its path relative to `dir`.
+///
+/// Package manifests are considered only if they embed the configuration.
+pub fn discover(dir: &Path) -> Option<PathBuf> {
+    let mut relative_dir = PathBuf::new();
+    for dir in dir.ancestors() {
+        for file_name in CONFIG_FILE_NAMES {
+            if hosts_config(&dir.join(file_name)) {
+                return Some(relative_dir.join(file_name));
+            }
+        }
+        if VCS_DIRS.iter().any(|vcs_dir| dir.join(vcs_dir).exists()) {
+            break;
+        }
+        relative_dir.push("..");
+    }
+    None
+}
+
+fn hosts_config(path: &Path) -> bool {
+    let host = ConfigHost::from_path(&path.to_string_lossy());
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return false,
+    };
+    match host {
+        ConfigHost::Standalone => true,
+        ConfigHost::Cargo | ConfigHost::Pyproject => toml::from_str::<toml::Value>(&content)
+            .ok()
+            .and_then(|value| {
+                host.key()
+                    .split('.')
+                    .try_fold(&value, |value, segment| value.get(segment))
+                    .cloned()
+            })
+            .is_some(),
+        ConfigHost::Npm => serde_json::from_str::<serde_json::Value>(&content)
+            .ok()
+            .and_then(|value| value.get(host.key()).cloned())
+            .is_some(),
+    }
+}
+
+/// The file hosting the configuration, either a standalone one with a top-level `semver` table or
+/// the manifest of a package.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ConfigHost {
+    Standalone,
+    /// `[package.metadata.semver]` of a `Cargo.toml`
+    Cargo,
+    /// `[tool.semver]` of a `pyproject.toml`
+    Pyproject,
+    /// `"semver"` of a `package.json`
+    Npm,
+}
+
+impl ConfigHost {
+    pub fn from_path(file_path: &str) -> Self {
+        match Path::new(file_path)
+            .file_name()
+            .and_then(|name| name.to_str())
+        {
+            Some("Cargo.toml") => ConfigHost::Cargo,
+            Some("pyproject.toml") => ConfigHost::Pyproject,
+            Some("package.json") => ConfigHost::Npm,
+            _ => ConfigHost::Standalone,
+        }
+    }
+
+    /// The dotted path of the configuration in the file.
+    pub fn key(&self) -> &'static str {
+        match self {
+            ConfigHost::Standalone | ConfigHost::Npm => "semver",
+            ConfigHost::Cargo => "package.metadata.semver",
+            ConfigHost::Pyproject => "tool.semver",
+        }
+    }
+
+    fn format(&self) -> FileFormat {
+        match self {
+            ConfigHost::Npm => FileFormat::Json,
+            _ => FileFormat::Toml,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -81,85 +364,469 @@ pub struct Config {
     pub default_part: core::Part,
     pub files: HashMap<String, FileConfig>,
     pub bump_prerelease_func: Option<String>,
-    raw_config: WrapperRawConfig,
-}
-
-impl From<WrapperRawConfig> for Config {
-    fn from(wrapper_config: WrapperRawConfig) -> Self {
-        let raw_config = wrapper_config.clone();
-        Config {
-            current_version: wrapper_config.semver.current_version,
-            last_stable_version: wrapper_config.semver.last_stable_version,
-            // TODO: Avoid `unwrap`
-            default_part: core::Part::from_str(&wrapper_config.semver.default_part).unwrap(),
-            files: wrapper_config
-                .semver
-                .files
-                .map_or(HashMap::new(), |files| files),
-            bump_prerelease_func: wrapper_config
-                .semver
+    pub bump_prerelease_command: Option<BumpCommand>,
+    pub prerelease_strategy: Option<core::PrereleaseStrategy>,
+    pub git: GitOptions,
+    pub host: ConfigHost,
+    /// The content the configuration has been parsed from, kept to preserve its formatting.
+    raw_content: String,
+}
+
+impl Config {
+    fn from_raw(
+        raw_config: RawConfig,
+        raw_content: &str,
+        host: ConfigHost,
+    ) -> Result<Self, ConfigError> {
+        #[cfg(not(feature = "js"))]
+        if raw_config
+            .prerelease
+            .as_ref()
+            .is_some_and(|prerel| prerel.bump_script.is_some())
+        {
+            return Err(ConfigError::UnsupportedBumpScript(format!(
+                "{}.prerelease.bump_script",
+                host.key()
+            )));
+        }
+        let default_part = core::Part::from_str(&raw_config.default_part).map_err(|_| {
+            ConfigError::InvalidPart(
+                format!("{}.default_part", host.key()),
+                raw_config.default_part.to_owned(),
+            )
+        })?;
+        Ok(Config {
+            current_version: raw_config.current_version,
+            last_stable_version: raw_config.last_stable_version,
+            default_part,
+            files: raw_config.files.map_or(HashMap::new(), |files| files),
+            bump_prerelease_func: raw_config
+                .prerelease
+                .as_ref()
+                .and_then(|prerel| prerel.bump_script.to_owned()),
+            bump_prerelease_command: raw_config
+                .prerelease
+                .as_ref()
+                .and_then(|prerel| prerel.command()),
+            prerelease_strategy: raw_config
                 .prerelease
-                .map(|prerel| prerel.bump_script),
+                .as_ref()
+                .and_then(|prerel| prerel.strategy()),
+            git: raw_config
+                .git
+                .as_ref()
+                .map_or_else(GitOptions::default, GitOptions::from),
             path: None,
-            raw_config,
-        }
+            host,
+            raw_content: raw_content.to_owned(),
+        })
+    }
+
+    /// Parses the configuration from the content of the file hosting it.
+    pub fn parse(content: &str, host: ConfigHost) -> Result<Self, ConfigError> {
+        let missing_section = || ConfigError::MissingSection(host.key().to_owned());
+        let raw_config: RawConfig = match host {
+            ConfigHost::Standalone => return Config::from_str(content),
+            ConfigHost::Cargo | ConfigHost::Pyproject => {
+                let value: toml::Value = toml::from_str(content)?;
+                host.key()
+                    .split('.')
+                    .try_fold(&value, |value, segment| value.get(segment))
+                    .ok_or_else(missing_section)?
+                    .clone()
+                    .try_into()?
+            }
+            ConfigHost::Npm => {
+                let value: serde_json::Value = serde_json::from_str(content)?;
+                serde_json::from_value(value.get(host.key()).ok_or_else(missing_section)?.clone())?
+            }
+        };
+        Config::from_raw(raw_config, content, host)
     }
 }
 
 impl str::FromStr for Config {
-    type Err = toml::de::Error;
+    type Err = ConfigError;
 
     fn from_str(raw_config: &str) -> Result<Self, Self::Err> {
         let wrapper_config: WrapperRawConfig = toml::from_str(raw_config)?;
-        Ok(Config::from(wrapper_config))
+        Config::from_raw(wrapper_config.semver, raw_config, ConfigHost::Standalone)
     }
 }
 
 impl Config {
+    /// Loads and validates the configuration from a file.
     pub fn from_file(file_path: &str) -> Result<Self, ConfigError> {
+        let config = Config::load(file_path)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Loads the configuration from a file without validating it.
+    pub fn load(file_path: &str) -> Result<Self, ConfigError> {
         let content = fs::read_to_string(file_path)?;
-        let mut config = Config::from_str(&content)
-            .map(|mut config| {
-                config.path = Some(file_path.to_string());
-                config
-            })
-            .map_err(ConfigError::from)?;
+        let mut config = Config::parse(&content, ConfigHost::from_path(file_path))?;
         config.path = Some(file_path.to_owned());
         Ok(config)
     }
 
+    /// Returns the first of the errors found by `check`, if any, except the ones about the files
+    /// missing and the prerelease script, which are only relevant when bumping.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        first_error(self.collect_errors(false))
+    }
+
+    /// Like `validate`, but also checks that the files exist and the prerelease script.
+    pub fn validate_all(&self) -> Result<(), ConfigError> {
+        first_error(self.check())
+    }
+
+    /// Checks that the versions are valid and ordered, that the files exist, that their templates
+    /// and the git ones can be rendered and that the prerelease script defines a `bump` function.
+    pub fn check(&self) -> Vec<ConfigError> {
+        self.collect_errors(true)
+    }
+
+    /// Collects the errors of the configuration, including the ones about the files missing and
+    /// the prerelease script, which requires running the script, only if `all`.
+    fn collect_errors(&self, all: bool) -> Vec<ConfigError> {
+        let key = |name: &str| format!("{}.{}", self.host.key(), name);
+        let mut errors = vec![];
+
+        let current_version = core::Version::from_str(&self.current_version)
+            .map_err(|err| ConfigError::InvalidVersion(key("current_version"), err.to_string()));
+        let last_stable_version = self
+            .last_stable_version
+            .as_ref()
+            .map(|last_stable_version| {
+                core::Version::from_str(last_stable_version).map_err(|err| {
+                    ConfigError::InvalidVersion(key("last_stable_version"), err.to_string())
+                })
+            });
+        if let (Ok(current_version), Some(Ok(last_stable_version))) =
+            (&current_version, &last_stable_version)
+        {
+            if last_stable_version.cmp_precedence(current_version) == Ordering::Greater {
+                errors.push(ConfigError::UnorderedVersions(
+                    key("last_stable_version"),
+                    format!(
+                        "'{}' is greater than the current version '{}'",
+                        last_stable_version, current_version
+                    ),
+                ));
+            }
+        }
+        errors.extend(current_version.err());
+        errors.extend(last_stable_version.and_then(|version| version.err()));
+
+        let context = template::Context::with_versions(
+            self.current_version.to_owned(),
+            self.current_version.to_owned(),
+            self.last_stable_version.to_owned().unwrap_or_default(),
+        );
+        let mut file_paths: Vec<&String> = self.files.keys().collect();
+        file_paths.sort_unstable();
+        for file_path in file_paths {
+            let file_key = key(&format!("files.\"{}\"", file_path));
+            if all && !Path::new(file_path).exists() {
+                errors.push(ConfigError::MissingFile(
+                    file_key.to_owned(),
+                    file_path.to_owned(),
+                ));
+            }
+            let file_config = &self.files[file_path];
+            if let Some((first, last)) = file_config.lines {
+                if first == 0 || last == 0 || first > last {
+                    errors.push(ConfigError::InvalidLines(
+                        format!("{}.lines", file_key),
+                        format!(
+                            "[{}, {}] is not a range of lines, which are 1-based and inclusive",
+                            first, last
+                        ),
+                    ));
+                }
+            }
+            // Each template is rendered against an empty content to check it in isolation
+            let search_regex = file_config.search_regex.unwrap_or(false);
+            for (name, search, replace) in [
+                ("search", file_config.search.as_deref(), Some("")),
+                ("replace", Some(""), file_config.replace.as_deref()),
+            ] {
+                let (search, replace) = match (search, replace) {
+                    (Some(search), Some(replace)) => (search, replace),
+                    _ => continue,
+                };
+                let rendered = if search_regex {
+                    template::replace_regex_with_count("", search, replace, &context, None)
+                } else {
+                    template::replace_content_with_count("", search, replace, &context, None)
+                };
+                if let Err(err) = rendered {
+                    errors.push(ConfigError::InvalidTemplate(
+                        format!("{}.{}", file_key, name),
+                        err.to_string(),
+                    ));
+                }
+            }
+        }
+
+        for (name, git_template) in [
+            ("git.message", &self.git.message),
+            ("git.tag_name", &self.git.tag_name),
+        ] {
+            match template::render(git_template, &context) {
+                Err(err) => errors.push(ConfigError::InvalidTemplate(key(name), err.to_string())),
+                // Checking the name of the tag requires running git
+                Ok(tag_name) if all && name == "git.tag_name" => {
+                    if let Ok(false) = git::is_valid_tag_name(&tag_name) {
+                        errors.push(ConfigError::InvalidTemplate(
+                            key(name),
+                            format!("'{}' is not a valid tag name", tag_name),
+                        ));
+                    }
+                }
+                Ok(_) => {}
+            }
+        }
+
+        let bump_funcs = [
+            self.bump_prerelease_func.is_some(),
+            self.bump_prerelease_command.is_some(),
+            self.prerelease_strategy.is_some(),
+        ];
+        if bump_funcs.iter().filter(|is_set| **is_set).count() > 1 {
+            errors.push(ConfigError::InvalidPrerelease(
+                key("prerelease"),
+                "Only one of `bump_script`, `bump_command` and `strategy` can be set".to_owned(),
+            ));
+        }
+        if let Some(command) = &self.bump_prerelease_command {
+            if command.args.is_empty() {
+                errors.push(ConfigError::InvalidPrerelease(
+                    key("prerelease.bump_command"),
+                    "Missing the program to run".to_owned(),
+                ));
+            }
+        }
+        match &self.prerelease_strategy {
+            Some(core::PrereleaseStrategy::Numeric(prefix))
+            | Some(core::PrereleaseStrategy::Timestamp(prefix))
+                if !validate::validate_part(&format!("{}1", prefix), Some(&PRERELEASE)) =>
+            {
+                errors.push(ConfigError::InvalidPrerelease(
+                    key("prerelease.prefix"),
+                    format!("'{}' cannot start a prerelease", prefix),
+                ));
+            }
+            Some(core::PrereleaseStrategy::Channel(channels)) => {
+                if channels.is_empty() {
+                    errors.push(ConfigError::InvalidPrerelease(
+                        key("prerelease.channels"),
+                        "There must be at least a channel".to_owned(),
+                    ));
+                }
+                for channel in channels {
+                    if !validate::validate_part(channel, Some(&PRERELEASE)) {
+                        errors.push(ConfigError::InvalidPrerelease(
+                            key("prerelease.channels"),
+                            format!("'{}' is not a valid prerelease", channel),
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+        #[cfg(feature = "js")]
+        if let Some(code) = self.bump_prerelease_func.as_ref().filter(|_| all) {
+            if let Err(msg) = check_bump_script(code) {
+                errors.push(ConfigError::InvalidBumpScript(
+                    key("prerelease.bump_script"),
+                    msg,
+                ));
+            }
+        }
+        errors
+    }
+
+    /// Makes the relative paths of the files and of the bump command relative to `dir`, e.g. to
+    /// the directory of a configuration file found in a parent directory.
+    pub fn rebase_paths(&mut self, dir: &Path) {
+        if let Some(program) = self
+            .bump_prerelease_command
+            .as_mut()
+            .and_then(|command| command.args.first_mut())
+        {
+            // A program without a directory is looked for in `PATH`
+            let path = Path::new(program);
+            if path.is_relative() && path.components().count() > 1 {
+                *program = dir.join(path).to_string_lossy().into_owned();
+            }
+        }
+        self.files = self
+            .files
+            .drain()
+            .map(|(file_path, file_config)| {
+                let path = Path::new(&file_path);
+                if path.is_relative() {
+                    (dir.join(path).to_string_lossy().into_owned(), file_config)
+                } else {
+                    (file_path, file_config)
+                }
+            })
+            .collect();
+    }
+
     pub fn update(&self, new_version: &core::Version) -> Result<Config, io::Error> {
-        let mut raw_config = self.raw_config.to_owned();
-        raw_config.semver.last_stable_version = if new_version.prerelease.is_some() {
+        let serialized_config = self.serialize_update(new_version);
+        if let Some(path) = &self.path {
+            fs::write(path, &serialized_config)?;
+        }
+        Ok(Config::parse(&serialized_config, self.host).unwrap())
+    }
+
+    /// Adds to `changes` the change to the configuration file that `update` would write, if the
+    /// configuration has been loaded from a file.
+    ///
+    /// If the file is already among the changes, e.g. a `Cargo.toml` hosting the configuration
+    /// and bumped through a preset, the versions are updated on top of its new content.
+    pub fn add_file_change(
+        &self,
+        new_version: &core::Version,
+        changes: &mut Vec<file::FileChange>,
+    ) -> Result<(), io::Error> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        match changes
+            .iter_mut()
+            .find(|change| is_same_file(&change.path, path))
+        {
+            Some(change) => {
+                change.new_content = self.update_content(&change.new_content, new_version)
+            }
+            None => changes.push(file::FileChange {
+                path: path.to_owned(),
+                original_content: fs::read_to_string(path)?,
+                new_content: self.serialize_update(new_version),
+                matches: 1,
+            }),
+        }
+        Ok(())
+    }
+
+    fn serialize_update(&self, new_version: &core::Version) -> String {
+        self.update_content(&self.raw_content, new_version)
+    }
+
+    /// Updates only the versions in the content hosting the configuration, preserving everything
+    /// else.
+    fn update_content(&self, content: &str, new_version: &core::Version) -> String {
+        let last_stable_version = if new_version.prerelease.is_some() {
             self.last_stable_version
                 .to_owned()
-                .or_else(|| Some(self.current_version.to_owned()))
+                .unwrap_or_else(|| self.current_version.to_owned())
         } else {
-            Some(new_version.to_string())
+            new_version.to_string()
         };
 
-        raw_config.semver.current_version = new_version.to_string();
-        // TODO: serialization to TOML doesn't preserve the order
-        let serialized_config = toml::to_string_pretty(&raw_config).unwrap();
-        match &self.path {
-            Some(path) => fs::write(path, &serialized_config)?,
-            None => (),
-        }
-        Ok(Config::from_str(&serialized_config).unwrap())
+        // The content has already been successfully parsed
+        let format = self.host.format();
+        let key = |name: &str| format!("{}.{}", self.host.key(), name);
+        let content = structured::insert_value(
+            content,
+            &format,
+            &key("current_version"),
+            &new_version.to_string(),
+        )
+        .unwrap();
+        structured::insert_value(
+            &content,
+            &format,
+            &key("last_stable_version"),
+            &last_stable_version,
+        )
+        .unwrap()
+    }
+}
+
+fn first_error(errors: Vec<ConfigError>) -> Result<(), ConfigError> {
+    match errors.into_iter().next() {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Checks that the script can be evaluated and that it defines a `bump` function.
+#[cfg(feature = "js")]
+fn check_bump_script(code: &str) -> Result<(), String> {
+    let code = format!(
+        "{}\nfunction __semverDefinesBump() {{ return typeof bump === 'function'; }}",
+        code
+    );
+    let mut script = Script::from_string(&code).map_err(|err| err.to_string())?;
+    match script.call::<_, bool>("__semverDefinesBump", &()) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("Missing 'bump' function".to_owned()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn is_same_file(path: &str, other_path: &str) -> bool {
+    match (fs::canonicalize(path), fs::canonicalize(other_path)) {
+        (Ok(path), Ok(other_path)) => path == other_path,
+        _ => path == other_path,
     }
 }
 
 #[derive(Debug)]
 pub enum ConfigError {
     ParseError(toml::de::Error),
+    JsonParseError(serde_json::Error),
+    MissingSection(String),
     IOError(io::Error),
+    InvalidPart(String, String),
+    InvalidVersion(String, String),
+    UnorderedVersions(String, String),
+    MissingFile(String, String),
+    InvalidTemplate(String, String),
+    InvalidBumpScript(String, String),
+    InvalidPrerelease(String, String),
+    InvalidLines(String, String),
+    UnsupportedBumpScript(String),
 }
 
 impl fmt::Display for ConfigError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ConfigError::ParseError(err) => write!(f, "{}", err),
+            ConfigError::JsonParseError(err) => write!(f, "{}", err),
+            ConfigError::MissingSection(key) => write!(f, "Missing '{}' section", key),
             ConfigError::IOError(err) => write!(f, "{}", err),
+            ConfigError::InvalidPart(key, part) => {
+                write!(f, "Invalid part '{}' at '{}'", part, key)
+            }
+            ConfigError::InvalidVersion(key, msg) => {
+                write!(f, "Invalid version at '{}': {}", key, msg)
+            }
+            ConfigError::UnorderedVersions(key, msg) => write!(f, "Invalid '{}': {}", key, msg),
+            ConfigError::MissingFile(key, file_path) => {
+                write!(f, "File '{}' at '{}' does not exist", file_path, key)
+            }
+            ConfigError::InvalidTemplate(key, msg) => {
+                write!(f, "Invalid template at '{}': {}", key, msg)
+            }
+            ConfigError::InvalidBumpScript(key, msg) => {
+                write!(f, "Invalid script at '{}': {}", key, msg)
+            }
+            ConfigError::InvalidPrerelease(key, msg) => write!(f, "Invalid '{}': {}", key, msg),
+            ConfigError::InvalidLines(key, msg) => write!(f, "Invalid '{}': {}", key, msg),
+            ConfigError::UnsupportedBumpScript(key) => write!(
+                f,
+                "Cannot use '{}' as semver has been built without the `js` feature, use \
+                 `bump_command` or `strategy` instead",
+                key
+            ),
         }
     }
 }
@@ -172,6 +839,12 @@ impl From<toml::de::Error> for ConfigError {
     }
 }
 
+impl From<serde_json::Error> for ConfigError {
+    fn from(err: serde_json::Error) -> Self {
+        ConfigError::JsonParseError(err)
+    }
+}
+
 impl From<io::Error> for ConfigError {
     fn from(err: io::Error) -> Self {
         ConfigError::IOError(err)
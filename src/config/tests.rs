@@ -1,4 +1,6 @@
-use super::{Config, FileConfig};
+use super::{
+    discover, Config, ConfigError, ConfigHost, FileConfig, FileFormat, GitOptions, Preset,
+};
 
 mod test_config_update {
     use super::Config;
@@ -75,9 +77,612 @@ default_part = "minor"
     }
 }
 
+mod test_config_file_change {
+    use super::Config;
+    use crate::core::Version;
+    use crate::file::FileChange;
+    use std::fs;
+    use std::str::FromStr;
+
+    const CONFIG: &str = r#"
+[semver]
+current_version = "1.0.0"
+default_part = "minor"
+"#;
+
+    #[test]
+    fn test_without_path() {
+        let config = Config::from_str(CONFIG).unwrap();
+        let mut changes = vec![];
+        config
+            .add_file_change(&Version::with_values(2, 0, 0, None), &mut changes)
+            .unwrap();
+        assert_eq!(changes, vec![]);
+    }
+
+    #[test]
+    fn test_with_path() {
+        let path = "./__test_config_file_change___test_with_path.toml";
+        fs::write(path, CONFIG).unwrap();
+        let config = Config::from_file(path).unwrap();
+        let mut changes = vec![];
+        let result = config.add_file_change(&Version::with_values(2, 0, 0, None), &mut changes);
+        fs::remove_file(path).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, path);
+        assert_eq!(changes[0].original_content, CONFIG);
+        let updated_config = Config::from_str(&changes[0].new_content).unwrap();
+        assert_eq!(updated_config.current_version, "2.0.0".to_owned());
+        assert_eq!(updated_config.last_stable_version, Some("2.0.0".to_owned()));
+    }
+
+    #[test]
+    fn test_with_changed_host() {
+        let path = "./__test_config_file_change___test_with_changed_host";
+        fs::create_dir_all(path).unwrap();
+        let file_path = format!("{}/Cargo.toml", path);
+        let content = r#"[package]
+name = "app"
+version = "1.0.0"
+
+[package.metadata.semver]
+current_version = "1.0.0"
+default_part = "minor"
+"#;
+        fs::write(&file_path, content).unwrap();
+        let config = Config::from_file(&file_path).unwrap();
+        let mut changes = vec![FileChange {
+            path: file_path.to_owned(),
+            original_content: content.to_owned(),
+            new_content: content.replacen("version = \"1.0.0\"", "version = \"2.0.0\"", 1),
+            matches: 1,
+        }];
+        let result = config.add_file_change(&Version::with_values(2, 0, 0, None), &mut changes);
+        fs::remove_dir_all(path).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].original_content, content);
+        assert_eq!(
+            changes[0].new_content,
+            r#"[package]
+name = "app"
+version = "2.0.0"
+
+[package.metadata.semver]
+current_version = "2.0.0"
+default_part = "minor"
+last_stable_version = "2.0.0"
+"#
+        );
+    }
+}
+
+mod test_config_hosts {
+    use super::{Config, ConfigError, ConfigHost};
+    use crate::core::Version;
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(ConfigHost::from_path("Cargo.toml"), ConfigHost::Cargo);
+        assert_eq!(
+            ConfigHost::from_path("lib/pyproject.toml"),
+            ConfigHost::Pyproject
+        );
+        assert_eq!(ConfigHost::from_path("./package.json"), ConfigHost::Npm);
+        assert_eq!(
+            ConfigHost::from_path(".semver.toml"),
+            ConfigHost::Standalone
+        );
+    }
+
+    #[test]
+    fn test_cargo() {
+        let content = r#"[package]
+name = "app"
+version = "1.0.0"
+
+[package.metadata.semver]
+current_version = "1.0.0"
+default_part = "minor"
+
+[package.metadata.semver.files."Cargo.toml"]
+preset = "cargo"
+"#;
+        let config = Config::parse(content, ConfigHost::Cargo).unwrap();
+        assert_eq!(config.current_version, "1.0.0");
+        assert_eq!(config.files.len(), 1);
+        assert_eq!(
+            config.serialize_update(&Version::with_values(1, 1, 0, None)),
+            r#"[package]
+name = "app"
+version = "1.0.0"
+
+[package.metadata.semver]
+current_version = "1.1.0"
+default_part = "minor"
+last_stable_version = "1.1.0"
+
+[package.metadata.semver.files."Cargo.toml"]
+preset = "cargo"
+"#
+        );
+    }
+
+    #[test]
+    fn test_pyproject() {
+        let content = r#"[project]
+name = "app"
+version = "1.0.0"
+
+[tool.semver]
+current_version = "1.0.0"
+last_stable_version = "1.0.0"
+default_part = "patch"
+"#;
+        let config = Config::parse(content, ConfigHost::Pyproject).unwrap();
+        assert_eq!(config.current_version, "1.0.0");
+        assert_eq!(
+            config.serialize_update(&Version::with_values(1, 0, 1, Some("rc.1".to_owned()))),
+            content.replace(
+                "current_version = \"1.0.0\"",
+                "current_version = \"1.0.1-rc.1\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_npm() {
+        let content = r#"{
+  "name": "app",
+  "version": "1.0.0",
+  "semver": {
+    "current_version": "1.0.0",
+    "default_part": "minor",
+    "files": {"package.json": {"preset": "npm"}}
+  }
+}
+"#;
+        let config = Config::parse(content, ConfigHost::Npm).unwrap();
+        assert_eq!(config.current_version, "1.0.0");
+        assert_eq!(config.files.len(), 1);
+        assert_eq!(
+            config.serialize_update(&Version::with_values(1, 1, 0, None)),
+            r#"{
+  "name": "app",
+  "version": "1.0.0",
+  "semver": {
+    "last_stable_version": "1.1.0",
+    "current_version": "1.1.0",
+    "default_part": "minor",
+    "files": {"package.json": {"preset": "npm"}}
+  }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_missing_section() {
+        for (content, host) in [
+            ("[package]\nname = \"app\"\n", ConfigHost::Cargo),
+            ("[tool.black]\n", ConfigHost::Pyproject),
+            (r#"{"name": "app"}"#, ConfigHost::Npm),
+        ] {
+            assert!(matches!(
+                Config::parse(content, host),
+                Err(ConfigError::MissingSection(key)) if key == host.key()
+            ));
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(matches!(
+            Config::parse("{", ConfigHost::Npm),
+            Err(ConfigError::JsonParseError(_))
+        ));
+        assert!(matches!(
+            Config::parse("[package", ConfigHost::Cargo),
+            Err(ConfigError::ParseError(_))
+        ));
+    }
+}
+
+mod test_config_discovery {
+    use super::{discover, Config};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::str::FromStr;
+
+    const CONFIG: &str = "[semver]\ncurrent_version = \"1.0.0\"\ndefault_part = \"minor\"\n";
+
+    /// Creates a repository with the given files and runs the test from its `a/b` subdirectory.
+    fn with_repo<F>(test_func_name: &str, files: &[(&str, &str)], test_func: F)
+    where
+        F: Fn(&Path),
+    {
+        let repo = format!("./__{}", test_func_name);
+        fs::create_dir_all(format!("{}/.git", repo)).unwrap();
+        fs::create_dir_all(format!("{}/a/b", repo)).unwrap();
+        for (file_path, content) in files {
+            fs::write(format!("{}/{}", repo, file_path), content).unwrap();
+        }
+        test_func(&Path::new(&repo).join("a/b"));
+        fs::remove_dir_all(repo).unwrap();
+    }
+
+    #[test]
+    fn test_in_parent() {
+        with_repo(
+            "test_config_discovery_in_parent",
+            &[(".semver.toml", CONFIG), ("a/semver.toml", CONFIG)],
+            |dir| assert_eq!(discover(dir), Some(PathBuf::from("../semver.toml"))),
+        );
+    }
+
+    #[test]
+    fn test_precedence() {
+        with_repo(
+            "test_config_discovery_precedence",
+            &[("semver.toml", CONFIG), (".semver.toml", CONFIG)],
+            |dir| assert_eq!(discover(dir), Some(PathBuf::from("../../.semver.toml"))),
+        );
+    }
+
+    #[test]
+    fn test_embedded() {
+        with_repo(
+            "test_config_discovery_embedded",
+            &[
+                ("a/b/Cargo.toml", "[package]\nname = \"app\"\n"),
+                ("a/package.json", r#"{"name": "app", "semver": {}}"#),
+            ],
+            |dir| assert_eq!(discover(dir), Some(PathBuf::from("../package.json"))),
+        );
+    }
+
+    #[test]
+    fn test_stop_at_repository_root() {
+        with_repo(
+            "test_config_discovery_stop_at_repository_root",
+            &[],
+            |dir| assert_eq!(discover(dir), None),
+        );
+    }
+
+    #[test]
+    fn test_rebase_paths() {
+        let mut config = Config::from_str(&format!(
+            "{}[semver.files.\"a.txt\"]\n[semver.files.\"/b.txt\"]\n",
+            CONFIG
+        ))
+        .unwrap();
+        config.rebase_paths(Path::new(".."));
+        let mut files: Vec<&String> = config.files.keys().collect();
+        files.sort_unstable();
+        assert_eq!(files, ["../a.txt", "/b.txt"]);
+
+        for (program, expected) in [
+            ("./next.sh", ".././next.sh"),
+            ("scripts/next.sh", "../scripts/next.sh"),
+            ("python3", "python3"),
+        ] {
+            let mut config = Config::from_str(&format!(
+                "{}[semver.prerelease]\nbump_command = [\"{}\", \"--dev\"]\n",
+                CONFIG, program
+            ))
+            .unwrap();
+            config.rebase_paths(Path::new(".."));
+            assert_eq!(
+                config.bump_prerelease_command.unwrap().args,
+                [expected, "--dev"]
+            );
+        }
+    }
+}
+
+mod test_config_validation {
+    use super::{Config, ConfigError};
+    use std::fs;
+    use std::str::FromStr;
+
+    fn check(content: &str) -> Vec<String> {
+        Config::from_str(content)
+            .unwrap()
+            .check()
+            .iter()
+            .map(|err| err.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_valid() {
+        let errors = check(
+            r#"
+[semver]
+current_version = "1.1.0-dev.1"
+last_stable_version = "1.0.0"
+default_part = "minor"
+
+[semver.files."Cargo.toml"]
+search = 'version = "{current_version}"'
+replace = 'version = "{new_version}"'
+search_regex = true
+"#,
+        );
+        assert_eq!(errors, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_versions() {
+        let errors = check(
+            r#"
+[semver]
+current_version = "1.0"
+last_stable_version = "latest"
+default_part = "minor"
+"#,
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "Invalid version at 'semver.current_version': Invalid version: 1.0",
+                "Invalid version at 'semver.last_stable_version': Invalid version: latest",
+            ]
+        );
+        let errors = check(
+            r#"
+[semver]
+current_version = "1.0.0"
+last_stable_version = "1.1.0"
+default_part = "minor"
+"#,
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "Invalid 'semver.last_stable_version': '1.1.0' is greater than the current version '1.0.0'"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_files() {
+        let errors = check(
+            r#"
+[semver]
+current_version = "1.0.0"
+default_part = "minor"
+
+[semver.files."missing.txt"]
+
+[semver.files."Cargo.toml"]
+search = "{unknown}"
+
+[semver.files."README.md"]
+search = "(v{current_version}"
+replace = "{new_version"
+search_regex = true
+"#,
+        );
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].starts_with("Invalid template at 'semver.files.\"Cargo.toml\".search'"));
+        assert!(errors[1].starts_with("Invalid template at 'semver.files.\"README.md\".search'"));
+        assert!(errors[2].starts_with("Invalid template at 'semver.files.\"README.md\".replace'"));
+        assert_eq!(
+            errors[3],
+            "File 'missing.txt' at 'semver.files.\"missing.txt\"' does not exist"
+        );
+    }
+
+    #[test]
+    fn test_validate_missing_file() {
+        let config = Config::from_str(
+            "[semver]\ncurrent_version = \"1.0.0\"\ndefault_part = \"minor\"\n[semver.files.\"missing.txt\"]\n",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert!(matches!(
+            config.validate_all(),
+            Err(ConfigError::MissingFile(_, file_path)) if file_path == "missing.txt"
+        ));
+    }
+
+    #[test]
+    fn test_embedded_key_path() {
+        let path = "./__test_config_validation___test_embedded_key_path";
+        fs::create_dir_all(path).unwrap();
+        let file_path = format!("{}/pyproject.toml", path);
+        fs::write(
+            &file_path,
+            "[tool.semver]\ncurrent_version = \"1\"\ndefault_part = \"minor\"\n",
+        )
+        .unwrap();
+        let config = Config::from_file(&file_path);
+        fs::remove_dir_all(path).unwrap();
+
+        assert!(matches!(
+            config,
+            Err(ConfigError::InvalidVersion(key, _)) if key == "tool.semver.current_version"
+        ));
+    }
+
+    #[test]
+    fn test_prerelease() {
+        let errors = check(
+            r#"
+[semver]
+current_version = "1.0.0"
+default_part = "minor"
+
+[semver.prerelease]
+bump_command = ["./next.sh"]
+strategy = "numeric"
+prefix = "dev_"
+"#,
+        );
+        assert_eq!(
+            errors[0],
+            "Invalid 'semver.prerelease': Only one of `bump_script`, `bump_command` and `strategy` can be set"
+        );
+        assert_eq!(
+            errors[1],
+            "Invalid 'semver.prerelease.prefix': 'dev_' cannot start a prerelease"
+        );
+
+        let errors = check(
+            r#"
+[semver]
+current_version = "1.0.0"
+default_part = "minor"
+
+[semver.prerelease]
+strategy = "channel"
+channels = ["alpha", "release candidate"]
+"#,
+        );
+        assert_eq!(
+            errors,
+            vec!["Invalid 'semver.prerelease.channels': 'release candidate' is not a valid prerelease"]
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        for lines in ["[0, 3]", "[3, 0]", "[3, 2]"] {
+            let errors = check(&format!(
+                "[semver]\ncurrent_version = \"1.0.0\"\ndefault_part = \"minor\"\n[semver.files.\"src/lib.rs\"]\nlines = {}\n",
+                lines
+            ));
+            assert_eq!(
+                errors.last().unwrap(),
+                &format!(
+                    "Invalid 'semver.files.\"src/lib.rs\".lines': {} is not a range of lines, which are 1-based and inclusive",
+                    lines
+                )
+            );
+        }
+        let config = Config::from_str(
+            "[semver]\ncurrent_version = \"1.0.0\"\ndefault_part = \"minor\"\n[semver.files.\"f.txt\"]\nlines = [2, 2]\n",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_git() {
+        let errors = check(
+            r#"
+[semver]
+current_version = "1.0.0"
+default_part = "minor"
+
+[semver.git]
+commit = true
+message = "Release {new_version"
+tag_name = "v{unknown}"
+"#,
+        );
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("Invalid template at 'semver.git.message'"));
+        assert!(errors[1].starts_with("Invalid template at 'semver.git.tag_name'"));
+
+        let errors = check(
+            r#"
+[semver]
+current_version = "1.0.0"
+default_part = "minor"
+
+[semver.git]
+tag_name = "release {new_version}"
+"#,
+        );
+        assert_eq!(
+            errors,
+            vec!["Invalid template at 'semver.git.tag_name': 'release 1.0.0' is not a valid tag name"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_part() {
+        assert!(matches!(
+            Config::from_str("[semver]\ncurrent_version = \"1.0.0\"\ndefault_part = \"build\"\n"),
+            Err(ConfigError::InvalidPart(key, part)) if key == "semver.default_part" && part == "build"
+        ));
+    }
+}
+
+mod test_config_preserve_format {
+    use super::Config;
+    use crate::core::Version;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_with_last_stable_version() {
+        let config = Config::from_str(
+            r#"# Managed by semver
+[semver]
+default_part = "minor"
+current_version = "1.1.0-dev.1"  # do not edit by hand
+last_stable_version = '1.0.0'
+
+# Files to bump
+[semver.files."test-1.txt"]
+search = "library = {current_version}"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.serialize_update(&Version::with_values(1, 1, 0, None)),
+            r#"# Managed by semver
+[semver]
+default_part = "minor"
+current_version = "1.1.0"  # do not edit by hand
+last_stable_version = "1.1.0"
+
+# Files to bump
+[semver.files."test-1.txt"]
+search = "library = {current_version}"
+"#
+        );
+    }
+
+    #[test]
+    fn test_without_last_stable_version() {
+        let config = Config::from_str(
+            r#"[semver]
+current_version = "1.0.0"
+default_part = "minor"
+
+[semver.files]
+
+[semver.files."test-1.txt"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.serialize_update(&Version::with_values(1, 1, 0, Some("dev.1".to_owned()))),
+            r#"[semver]
+current_version = "1.1.0-dev.1"
+default_part = "minor"
+last_stable_version = "1.0.0"
+
+[semver.files]
+
+[semver.files."test-1.txt"]
+"#
+        );
+    }
+}
+
 mod test_config_parsing {
-    use super::{Config, FileConfig};
-    use crate::core::{CorePart, Part};
+    #[cfg(not(feature = "js"))]
+    use super::ConfigError;
+    use super::{Config, FileConfig, FileFormat, GitOptions, Preset};
+    use crate::core::{CorePart, Part, PrereleaseStrategy};
     use std::str::FromStr;
 
     #[test]
@@ -159,7 +764,7 @@ replace = "library = {new_version}"
         );
 
         for config in [config_1, config_2] {
-            assert!(matches!(config, Err(_)));
+            assert!(config.is_err());
         }
     }
 
@@ -210,6 +815,7 @@ default_part = "minor"
     }
 
     #[test]
+    #[cfg(feature = "js")]
     fn test_with_prerelease() {
         let config = Config::from_str(
             r#"
@@ -270,4 +876,130 @@ function bump(version) {
             ),)
         );
     }
+
+    #[test]
+    #[cfg(not(feature = "js"))]
+    fn test_with_prerelease_without_js() {
+        assert!(matches!(
+            Config::from_str(
+                "[semver]\ncurrent_version = \"1.0.0\"\ndefault_part = \"minor\"\n[semver.prerelease]\nbump_script = \"\"\n"
+            ),
+            Err(ConfigError::UnsupportedBumpScript(key)) if key == "semver.prerelease.bump_script"
+        ));
+    }
+
+    #[test]
+    fn test_with_prerelease_strategy() {
+        let config = |prerelease: &str| {
+            Config::from_str(&format!(
+                "[semver]\ncurrent_version = \"1.0.0\"\ndefault_part = \"minor\"\n[semver.prerelease]\n{}",
+                prerelease
+            ))
+            .unwrap()
+        };
+        let test_cases = vec![
+            (
+                "strategy = \"numeric\"\nprefix = \"rc.\"",
+                PrereleaseStrategy::Numeric("rc.".to_owned()),
+            ),
+            (
+                "strategy = \"timestamp\"",
+                PrereleaseStrategy::Timestamp("".to_owned()),
+            ),
+            (
+                "strategy = \"channel\"",
+                PrereleaseStrategy::Channel(vec![
+                    "alpha".to_owned(),
+                    "beta".to_owned(),
+                    "rc".to_owned(),
+                ]),
+            ),
+            (
+                "strategy = \"channel\"\nchannels = [\"beta\", \"rc\"]",
+                PrereleaseStrategy::Channel(vec!["beta".to_owned(), "rc".to_owned()]),
+            ),
+        ];
+        for (prerelease, expected) in test_cases {
+            let config = config(prerelease);
+            assert_eq!(config.prerelease_strategy, Some(expected));
+            assert_eq!(config.bump_prerelease_func, None);
+        }
+        assert!(Config::from_str(
+            "[semver]\ncurrent_version = \"1.0.0\"\ndefault_part = \"minor\"\n[semver.prerelease]\nstrategy = \"random\"\n"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_with_git() {
+        let config = |git: &str| {
+            Config::from_str(&format!(
+                "[semver]\ncurrent_version = \"1.0.0\"\ndefault_part = \"minor\"\n{}",
+                git
+            ))
+            .unwrap()
+            .git
+        };
+        assert_eq!(config(""), GitOptions::default());
+        assert_eq!(
+            config(
+                "[semver.git]\ncommit = true\ntag = true\ntag_name = \"release-{new_version}\"\n"
+            ),
+            GitOptions {
+                commit: true,
+                tag: true,
+                message: "Bump version: {current_version} → {new_version}".to_owned(),
+                tag_name: "release-{new_version}".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_with_presets() {
+        let config = Config::from_str(
+            r#"
+[semver]
+current_version = "1.0.0"
+default_part = "minor"
+
+[semver.files."Cargo.toml"]
+preset = "cargo"
+
+[semver.files."setup.cfg"]
+preset = "setup.cfg"
+
+[semver.files."Chart.yaml"]
+preset = "helm-chart"
+key = "appVersion"
+
+[semver.files."values.yaml"]
+format = "yaml"
+key = "image.tag"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.files.get("Cargo.toml"),
+            Some(&FileConfig::with_preset(Preset::Cargo))
+        );
+        assert_eq!(
+            config.files.get("setup.cfg"),
+            Some(&FileConfig::with_preset(Preset::SetupCfg))
+        );
+        assert_eq!(
+            config.files.get("Chart.yaml"),
+            Some(&FileConfig {
+                key: Some("appVersion".to_owned()),
+                ..FileConfig::with_preset(Preset::HelmChart)
+            })
+        );
+        assert_eq!(
+            config.files.get("values.yaml"),
+            Some(&FileConfig::with_key(
+                FileFormat::Yaml,
+                "image.tag".to_owned()
+            ))
+        );
+    }
 }
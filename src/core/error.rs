@@ -1,15 +1,31 @@
 use anyhow;
-use std::num;
+use std::{fmt, num};
 
 #[derive(Debug, PartialEq)]
 pub enum VersionError {
     InvalidVersion(String),
     UnexpectedError(String),
     UnsupportedVersion(String),
+    InvalidRequirement(String),
     ParsingError(num::ParseIntError),
     BumpError(BumpError),
 }
 
+impl fmt::Display for VersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VersionError::InvalidVersion(msg) => write!(f, "Invalid version: {}", msg),
+            VersionError::UnexpectedError(msg) => write!(f, "{}", msg),
+            VersionError::UnsupportedVersion(msg) => write!(f, "Unsupported version: {}", msg),
+            VersionError::InvalidRequirement(msg) => write!(f, "{}", msg),
+            VersionError::ParsingError(err) => write!(f, "{}", err),
+            VersionError::BumpError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for VersionError {}
+
 impl From<num::ParseIntError> for VersionError {
     fn from(err: num::ParseIntError) -> VersionError {
         VersionError::ParsingError(err)
@@ -26,7 +42,9 @@ impl From<BumpError> for VersionError {
 pub enum BumpError {
     AnyError(anyhow::Error),
     MissingBumpScript,
+    MissingBuildMetadata,
     InvalidOperation(String),
+    CommandError(String),
 }
 
 impl PartialEq for BumpError {
@@ -35,12 +53,28 @@ impl PartialEq for BumpError {
             (BumpError::AnyError(_), _) => false,
             (_, BumpError::AnyError(_)) => false,
             (BumpError::MissingBumpScript, BumpError::MissingBumpScript) => true,
+            (BumpError::MissingBuildMetadata, BumpError::MissingBuildMetadata) => true,
             (BumpError::InvalidOperation(m1), BumpError::InvalidOperation(m2)) => m1 == m2,
+            (BumpError::CommandError(m1), BumpError::CommandError(m2)) => m1 == m2,
             _ => false,
         }
     }
 }
 
+impl fmt::Display for BumpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BumpError::AnyError(err) => write!(f, "{}", err),
+            BumpError::MissingBumpScript => write!(f, "Missing bump script"),
+            BumpError::MissingBuildMetadata => write!(f, "Missing build metadata"),
+            BumpError::InvalidOperation(msg) => write!(f, "{}", msg),
+            BumpError::CommandError(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for BumpError {}
+
 impl From<anyhow::Error> for BumpError {
     fn from(err: anyhow::Error) -> BumpError {
         BumpError::AnyError(err)
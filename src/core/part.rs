@@ -48,6 +48,7 @@ impl str::FromStr for CorePart {
 #[serde(rename_all = "lowercase")]
 pub enum ExtensionPart {
     Prerelease,
+    BuildMetadata,
 }
 
 impl str::FromStr for ExtensionPart {
@@ -56,6 +57,7 @@ impl str::FromStr for ExtensionPart {
     fn from_str(part: &str) -> Result<Self, Self::Err> {
         match part {
             "prerelease" => Ok(ExtensionPart::Prerelease),
+            "buildmetadata" => Ok(ExtensionPart::BuildMetadata),
             _ => Err(InvalidPartError {
                 part: part.to_string(),
             }),
@@ -70,6 +72,7 @@ impl fmt::Display for Part {
             Self::Core(CorePart::Minor) => write!(f, "minor"),
             Self::Core(CorePart::Patch) => write!(f, "patch"),
             Self::Extension(ExtensionPart::Prerelease) => write!(f, "prerelease"),
+            Self::Extension(ExtensionPart::BuildMetadata) => write!(f, "buildmetadata"),
         }
     }
 }
@@ -83,6 +86,7 @@ impl str::FromStr for Part {
             "minor" => Ok(Part::Core(CorePart::Minor)),
             "patch" => Ok(Part::Core(CorePart::Patch)),
             "prerelease" => Ok(Part::Extension(ExtensionPart::Prerelease)),
+            "buildmetadata" => Ok(Part::Extension(ExtensionPart::BuildMetadata)),
             _ => Err(InvalidPartError {
                 part: part.to_string(),
             }),
@@ -0,0 +1,72 @@
+use crate::core::{BumpError, ExtensionBumpFunc, Version};
+use chrono::Utc;
+
+/// Channels used by the `Channel` strategy when not provided.
+pub const DEFAULT_CHANNELS: [&str; 3] = ["alpha", "beta", "rc"];
+
+/// Built-in logic for bumping the prerelease, as an alternative to a bump script.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PrereleaseStrategy {
+    /// A counter after the prefix, e.g. `dev.1`, `dev.2` and so on.
+    Numeric(String),
+    /// The UTC time of the bump after the prefix, e.g. `dev.20220619103000`.
+    Timestamp(String),
+    /// The next of the channels, e.g. `alpha`, `beta` and then `rc`.
+    Channel(Vec<String>),
+}
+
+impl PrereleaseStrategy {
+    /// Returns the prerelease following the one of `version`, or the first one if `version` is not
+    /// a prerelease.
+    pub fn bump(&self, version: &Version) -> Result<String, BumpError> {
+        let prerelease = version.prerelease.as_deref();
+        match self {
+            PrereleaseStrategy::Numeric(prefix) => {
+                let counter = match prerelease {
+                    None => 0,
+                    Some(prerelease) => prerelease
+                        .strip_prefix(prefix.as_str())
+                        .and_then(|counter| counter.parse::<usize>().ok())
+                        .ok_or_else(|| {
+                            BumpError::InvalidOperation(format!(
+                                "Cannot bump prerelease '{}', expected a number after '{}'",
+                                prerelease, prefix
+                            ))
+                        })?,
+                };
+                Ok(format!("{}{}", prefix, counter + 1))
+            }
+            PrereleaseStrategy::Timestamp(prefix) => {
+                Ok(format!("{}{}", prefix, Utc::now().format("%Y%m%d%H%M%S")))
+            }
+            PrereleaseStrategy::Channel(channels) => {
+                let next = match prerelease {
+                    None => channels.first(),
+                    Some(prerelease) => {
+                        let position = channels
+                            .iter()
+                            .position(|channel| channel == prerelease)
+                            .ok_or_else(|| {
+                                BumpError::InvalidOperation(format!(
+                                    "Cannot bump prerelease '{}', it's not among the channels",
+                                    prerelease
+                                ))
+                            })?;
+                        channels.get(position + 1)
+                    }
+                };
+                next.cloned().ok_or_else(|| {
+                    BumpError::InvalidOperation(format!(
+                        "Cannot bump prerelease '{}', it's the last channel",
+                        prerelease.unwrap_or_default()
+                    ))
+                })
+            }
+        }
+    }
+
+    pub fn bump_func(&self) -> Box<dyn ExtensionBumpFunc> {
+        let strategy = self.to_owned();
+        Box::new(move |context| strategy.bump(&context.version))
+    }
+}
@@ -0,0 +1,285 @@
+use crate::core::{Version, VersionError};
+use regex::Regex;
+use std::cmp::Ordering;
+use std::str;
+
+const PARTIAL_PATTERN: &str = r"^(?P<op>>=|<=|>|<|=|\^|~>|~)?v?(?P<major>0|[1-9]\d*|[xX*])(?:\.(?P<minor>0|[1-9]\d*|[xX*]))?(?:\.(?P<patch>0|[1-9]\d*|[xX*]))?(?:-(?P<prerelease>(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)(?:\.(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*))?(?:\+(?P<buildmetadata>[0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*))?$";
+const OPERATORS: [&str; 8] = [">=", "<=", ">", "<", "=", "^", "~>", "~"];
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+}
+
+impl Op {
+    fn from_symbol(symbol: &str) -> Self {
+        match symbol {
+            ">" => Op::Greater,
+            ">=" => Op::GreaterEq,
+            "<" => Op::Less,
+            "<=" => Op::LessEq,
+            "~" | "~>" => Op::Tilde,
+            "^" => Op::Caret,
+            _ => Op::Exact,
+        }
+    }
+}
+
+/// A possibly incomplete version such as `1`, `1.2` or `1.x`, where `None` stands for a wildcard.
+#[derive(Debug)]
+struct Partial {
+    major: Option<usize>,
+    minor: Option<usize>,
+    patch: Option<usize>,
+    prerelease: Option<String>,
+}
+
+impl Partial {
+    fn is_full(&self) -> bool {
+        self.patch.is_some()
+    }
+
+    fn lower(&self) -> Version {
+        Version::with_values(
+            self.major.unwrap_or(0),
+            self.minor.unwrap_or(0),
+            self.patch.unwrap_or(0),
+            self.prerelease.to_owned(),
+        )
+    }
+
+    /// The first version not matched by the partial, excluding its prereleases.
+    fn upper(&self) -> Result<Version, VersionError> {
+        match (self.major, self.minor, self.patch) {
+            (Some(major), None, _) => Ok(exclusive_bound(increment(major)?, 0, 0)),
+            (Some(major), Some(minor), None) => Ok(exclusive_bound(major, increment(minor)?, 0)),
+            _ => Ok(self.lower()),
+        }
+    }
+}
+
+fn exclusive_bound(major: usize, minor: usize, patch: usize) -> Version {
+    Version::with_values(major, minor, patch, Some("0".to_owned()))
+}
+
+/// Returns the number following `value`, which is an error if `value` is the greatest one.
+fn increment(value: usize) -> Result<usize, VersionError> {
+    value
+        .checked_add(1)
+        .ok_or_else(|| VersionError::InvalidRequirement(format!("Number too large: {}", value)))
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Cmp {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Comparator {
+    cmp: Cmp,
+    version: Version,
+}
+
+impl Comparator {
+    fn new(cmp: Cmp, version: Version) -> Self {
+        Comparator { cmp, version }
+    }
+
+    fn nothing() -> Self {
+        Comparator::new(Cmp::Lt, exclusive_bound(0, 0, 0))
+    }
+
+    fn matches(&self, version: &Version) -> bool {
+        let ord = version.cmp_precedence(&self.version);
+        match self.cmp {
+            Cmp::Eq => ord == Ordering::Equal,
+            Cmp::Gt => ord == Ordering::Greater,
+            Cmp::Ge => ord != Ordering::Less,
+            Cmp::Lt => ord == Ordering::Less,
+            Cmp::Le => ord != Ordering::Greater,
+        }
+    }
+
+    fn from_partial(op: Op, p: &Partial) -> Result<Vec<Self>, VersionError> {
+        let major = match p.major {
+            Some(major) => major,
+            None => {
+                return Ok(match op {
+                    Op::Greater | Op::Less => vec![Comparator::nothing()],
+                    _ => vec![],
+                })
+            }
+        };
+        Ok(match op {
+            Op::Exact if p.is_full() => vec![Comparator::new(Cmp::Eq, p.lower())],
+            Op::Exact => vec![
+                Comparator::new(Cmp::Ge, p.lower()),
+                Comparator::new(Cmp::Lt, p.upper()?),
+            ],
+            Op::Greater if p.is_full() => vec![Comparator::new(Cmp::Gt, p.lower())],
+            Op::Greater => {
+                let mut upper = p.upper()?;
+                upper.prerelease = None;
+                vec![Comparator::new(Cmp::Ge, upper)]
+            }
+            Op::GreaterEq => vec![Comparator::new(Cmp::Ge, p.lower())],
+            Op::Less if p.is_full() => vec![Comparator::new(Cmp::Lt, p.lower())],
+            Op::Less => {
+                let mut lower = p.lower();
+                lower.prerelease = Some("0".to_owned());
+                vec![Comparator::new(Cmp::Lt, lower)]
+            }
+            Op::LessEq if p.is_full() => vec![Comparator::new(Cmp::Le, p.lower())],
+            Op::LessEq => vec![Comparator::new(Cmp::Lt, p.upper()?)],
+            Op::Tilde => {
+                let upper = match p.minor {
+                    Some(minor) => exclusive_bound(major, increment(minor)?, 0),
+                    None => exclusive_bound(increment(major)?, 0, 0),
+                };
+                vec![
+                    Comparator::new(Cmp::Ge, p.lower()),
+                    Comparator::new(Cmp::Lt, upper),
+                ]
+            }
+            Op::Caret => {
+                let upper = match (major, p.minor, p.patch) {
+                    (0, Some(0), Some(patch)) => exclusive_bound(0, 0, increment(patch)?),
+                    (0, Some(minor), _) => exclusive_bound(0, increment(minor)?, 0),
+                    _ => exclusive_bound(increment(major)?, 0, 0),
+                };
+                vec![
+                    Comparator::new(Cmp::Ge, p.lower()),
+                    Comparator::new(Cmp::Lt, upper),
+                ]
+            }
+        })
+    }
+
+    fn from_hyphen_range(from: &Partial, to: &Partial) -> Result<Vec<Self>, VersionError> {
+        let mut comparators = Comparator::from_partial(Op::GreaterEq, from)?;
+        comparators.extend(Comparator::from_partial(Op::LessEq, to)?);
+        Ok(comparators)
+    }
+}
+
+/// A version requirement made of comparator sets joined by `||`, such as `^1.2`, `~1.2.3`,
+/// `>=1.0.0 <2.0.0`, `1.x` or `1.2.3 - 2.3.4`.
+///
+/// A version satisfies the requirement if it matches all the comparators of any of the sets.
+/// As per npm semantics, a prerelease version only matches a set if at least one of its
+/// comparators refers to a prerelease of the same `major.minor.patch`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Requirement {
+    sets: Vec<Vec<Comparator>>,
+}
+
+impl Requirement {
+    pub fn matches(&self, version: &Version) -> bool {
+        self.sets.iter().any(|set| Self::set_matches(set, version))
+    }
+
+    fn set_matches(set: &[Comparator], version: &Version) -> bool {
+        if !set.iter().all(|comparator| comparator.matches(version)) {
+            return false;
+        }
+        version.prerelease.is_none()
+            || set.iter().any(|comparator| {
+                comparator.version.prerelease.is_some()
+                    && comparator.version.major == version.major
+                    && comparator.version.minor == version.minor
+                    && comparator.version.patch == version.patch
+            })
+    }
+
+    fn parse_set(raw_set: &str) -> Result<Vec<Comparator>, VersionError> {
+        let tokens = Self::tokenize(raw_set);
+        match tokens.as_slice() {
+            [from, hyphen, to] if hyphen == "-" => Comparator::from_hyphen_range(
+                &Self::parse_partial(from)?.1,
+                &Self::parse_partial(to)?.1,
+            ),
+            _ => {
+                let mut comparators = vec![];
+                for token in tokens {
+                    let (op, partial) = Self::parse_partial(&token)?;
+                    comparators.extend(Comparator::from_partial(op, &partial)?);
+                }
+                Ok(comparators)
+            }
+        }
+    }
+
+    /// Splits a comparator set on whitespaces and commas, joining operators separated from
+    /// their version (e.g. `>= 1.2.3`).
+    fn tokenize(raw_set: &str) -> Vec<String> {
+        let mut tokens: Vec<String> = vec![];
+        for word in raw_set
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|w| !w.is_empty())
+        {
+            match tokens.last_mut() {
+                Some(last) if OPERATORS.contains(&last.as_str()) => last.push_str(word),
+                _ => tokens.push(word.to_owned()),
+            }
+        }
+        tokens
+    }
+
+    fn parse_partial(raw_partial: &str) -> Result<(Op, Partial), VersionError> {
+        let re = Regex::new(PARTIAL_PATTERN).unwrap();
+        let caps = re.captures(raw_partial).ok_or_else(|| {
+            VersionError::InvalidRequirement(format!("Invalid comparator: {}", raw_partial))
+        })?;
+        // Only `x`, `X` and `*` are wildcards, while numbers too large are invalid
+        let extract = |name: &str| match caps.name(name).map(|found| found.as_str()) {
+            None | Some("x") | Some("X") | Some("*") => Ok(None),
+            Some(number) => number.parse::<usize>().map(Some).map_err(|_| {
+                VersionError::InvalidRequirement(format!("Number too large: {}", number))
+            }),
+        };
+        let major = extract("major")?;
+        let minor = major.and(extract("minor")?);
+        let patch = minor.and(extract("patch")?);
+        let prerelease = match (patch, caps.name("prerelease")) {
+            (Some(_), Some(found)) => Some(found.as_str().to_owned()),
+            (None, Some(_)) => {
+                return Err(VersionError::InvalidRequirement(format!(
+                    "Prerelease on a partial version: {}",
+                    raw_partial
+                )))
+            }
+            _ => None,
+        };
+        Ok((
+            Op::from_symbol(caps.name("op").map_or("", |found| found.as_str())),
+            Partial {
+                major,
+                minor,
+                patch,
+                prerelease,
+            },
+        ))
+    }
+}
+
+impl str::FromStr for Requirement {
+    type Err = VersionError;
+
+    fn from_str(raw_requirement: &str) -> Result<Self, Self::Err> {
+        let sets = raw_requirement
+            .split("||")
+            .map(Self::parse_set)
+            .collect::<Result<Vec<Vec<Comparator>>, VersionError>>()?;
+        Ok(Requirement { sets })
+    }
+}
@@ -1,2 +1,4 @@
+mod test_prerelease;
+mod test_requirement;
 mod test_version;
 mod test_version_manager;
@@ -0,0 +1,109 @@
+use crate::core::{
+    BumpError, CorePart, ExtensionPart, Part, PrereleaseStrategy, Version, VersionManager,
+};
+use regex::Regex;
+
+struct TestCase {
+    strategy: PrereleaseStrategy,
+    prerelease: Option<&'static str>,
+    expected: Result<&'static str, BumpError>,
+}
+
+impl TestCase {
+    pub fn new(
+        strategy: PrereleaseStrategy,
+        prerelease: Option<&'static str>,
+        expected: Result<&'static str, BumpError>,
+    ) -> Self {
+        TestCase {
+            strategy,
+            prerelease,
+            expected,
+        }
+    }
+}
+
+fn channels() -> Vec<String> {
+    vec!["alpha".to_owned(), "beta".to_owned(), "rc".to_owned()]
+}
+
+#[test]
+fn test_bump() {
+    let numeric = PrereleaseStrategy::Numeric("dev.".to_owned());
+    let channel = PrereleaseStrategy::Channel(channels());
+    let test_cases = vec![
+        TestCase::new(numeric.clone(), None, Ok("dev.1")),
+        TestCase::new(numeric.clone(), Some("dev.1"), Ok("dev.2")),
+        TestCase::new(numeric.clone(), Some("dev.9"), Ok("dev.10")),
+        TestCase::new(
+            PrereleaseStrategy::Numeric("".to_owned()),
+            Some("3"),
+            Ok("4"),
+        ),
+        TestCase::new(
+            numeric.clone(),
+            Some("rc.1"),
+            Err(BumpError::InvalidOperation(
+                "Cannot bump prerelease 'rc.1', expected a number after 'dev.'".to_owned(),
+            )),
+        ),
+        TestCase::new(
+            numeric,
+            Some("dev.x"),
+            Err(BumpError::InvalidOperation(
+                "Cannot bump prerelease 'dev.x', expected a number after 'dev.'".to_owned(),
+            )),
+        ),
+        TestCase::new(channel.clone(), None, Ok("alpha")),
+        TestCase::new(channel.clone(), Some("alpha"), Ok("beta")),
+        TestCase::new(channel.clone(), Some("beta"), Ok("rc")),
+        TestCase::new(
+            channel.clone(),
+            Some("rc"),
+            Err(BumpError::InvalidOperation(
+                "Cannot bump prerelease 'rc', it's the last channel".to_owned(),
+            )),
+        ),
+        TestCase::new(
+            channel,
+            Some("dev"),
+            Err(BumpError::InvalidOperation(
+                "Cannot bump prerelease 'dev', it's not among the channels".to_owned(),
+            )),
+        ),
+    ];
+    for tc in test_cases {
+        let version = Version::with_values(1, 0, 0, tc.prerelease.map(|p| p.to_owned()));
+        assert_eq!(
+            tc.strategy.bump(&version),
+            tc.expected.map(|expected| expected.to_owned())
+        );
+    }
+}
+
+#[test]
+fn test_bump_timestamp() {
+    let strategy = PrereleaseStrategy::Timestamp("nightly.".to_owned());
+    let re = Regex::new(r"^nightly\.\d{14}$").unwrap();
+    for prerelease in [None, Some("nightly.20220619103000".to_owned())] {
+        let prerelease = strategy
+            .bump(&Version::with_values(1, 0, 0, prerelease))
+            .unwrap();
+        assert!(re.is_match(&prerelease), "{}", prerelease);
+    }
+}
+
+#[test]
+fn test_version_manager() {
+    let vm = VersionManager::with_extension_bump_func(Some(
+        PrereleaseStrategy::Channel(channels()).bump_func(),
+    ));
+    let version = vm
+        .new_prerelease(&Version::with_values(1, 2, 3, None), &CorePart::Minor)
+        .unwrap();
+    assert_eq!(version.to_string(), "1.3.0-alpha");
+    let version = vm
+        .bump(&version, &Part::Extension(ExtensionPart::Prerelease))
+        .unwrap();
+    assert_eq!(version.to_string(), "1.3.0-beta");
+}
@@ -0,0 +1,160 @@
+use crate::core::{Requirement, Version, VersionError};
+
+fn assert_matches(requirement: &str, matching: Vec<&str>, not_matching: Vec<&str>) {
+    let req = requirement.parse::<Requirement>().unwrap();
+    for v in matching {
+        assert!(
+            req.matches(&v.parse::<Version>().unwrap()),
+            "'{}' should match '{}'",
+            v,
+            requirement
+        );
+    }
+    for v in not_matching {
+        assert!(
+            !req.matches(&v.parse::<Version>().unwrap()),
+            "'{}' should not match '{}'",
+            v,
+            requirement
+        );
+    }
+}
+
+#[test]
+fn test_exact() {
+    assert_matches(
+        "1.2.3",
+        vec!["1.2.3", "1.2.3+build.1"],
+        vec!["1.2.4", "1.2.3-rc.1"],
+    );
+    assert_matches("=1.2.3", vec!["1.2.3"], vec!["1.2.2"]);
+    assert_matches("1.2", vec!["1.2.0", "1.2.9"], vec!["1.3.0", "1.1.9"]);
+    assert_matches("1", vec!["1.0.0", "1.9.9"], vec!["2.0.0", "0.9.9"]);
+}
+
+#[test]
+fn test_comparison() {
+    assert_matches(">1.2.3", vec!["1.2.4", "2.0.0"], vec!["1.2.3", "1.0.0"]);
+    assert_matches(">=1.2.3", vec!["1.2.3", "2.0.0"], vec!["1.2.2"]);
+    assert_matches(
+        "<1.2.3",
+        vec!["1.2.2", "0.1.0"],
+        vec!["1.2.3", "1.2.3-rc.1"],
+    );
+    assert_matches("<=1.2.3", vec!["1.2.3", "0.1.0"], vec!["1.2.4"]);
+    assert_matches(">1.2", vec!["1.3.0"], vec!["1.2.9"]);
+    assert_matches("<=1.2", vec!["1.2.9"], vec!["1.3.0"]);
+    assert_matches("<1.2", vec!["1.1.9"], vec!["1.2.0"]);
+    assert_matches(
+        ">=1.0.0 <2.0.0",
+        vec!["1.0.0", "1.9.9"],
+        vec!["0.9.9", "2.0.0", "2.0.0-rc.1"],
+    );
+    assert_matches(">= 1.0.0, < 2.0.0", vec!["1.5.0"], vec!["2.0.0"]);
+}
+
+#[test]
+fn test_caret() {
+    assert_matches("^1.2.3", vec!["1.2.3", "1.9.0"], vec!["1.2.2", "2.0.0"]);
+    assert_matches("^1.2", vec!["1.2.0", "1.9.0"], vec!["1.1.9", "2.0.0"]);
+    assert_matches("^1", vec!["1.0.0", "1.9.0"], vec!["2.0.0"]);
+    assert_matches("^0.2.3", vec!["0.2.3", "0.2.9"], vec!["0.3.0", "0.2.2"]);
+    assert_matches("^0.0.3", vec!["0.0.3"], vec!["0.0.4"]);
+    assert_matches("^0.0", vec!["0.0.0", "0.0.9"], vec!["0.1.0"]);
+    assert_matches("^0", vec!["0.9.9"], vec!["1.0.0"]);
+    assert_matches("^1.x", vec!["1.5.0"], vec!["2.0.0"]);
+}
+
+#[test]
+fn test_tilde() {
+    assert_matches("~1.2.3", vec!["1.2.3", "1.2.9"], vec!["1.3.0", "1.2.2"]);
+    assert_matches("~1.2", vec!["1.2.0", "1.2.9"], vec!["1.3.0"]);
+    assert_matches("~1", vec!["1.0.0", "1.9.9"], vec!["2.0.0"]);
+    assert_matches("~>1.2.3", vec!["1.2.9"], vec!["1.3.0"]);
+}
+
+#[test]
+fn test_wildcard() {
+    assert_matches("*", vec!["0.0.0", "1.2.3"], vec!["1.2.3-rc.1"]);
+    assert_matches("", vec!["1.2.3"], vec![]);
+    assert_matches("1.x", vec!["1.0.0", "1.9.9"], vec!["2.0.0"]);
+    assert_matches("1.2.*", vec!["1.2.0", "1.2.9"], vec!["1.3.0"]);
+    assert_matches("1.X.3", vec!["1.0.0", "1.9.9"], vec!["2.0.0"]);
+}
+
+#[test]
+fn test_hyphen_range() {
+    assert_matches(
+        "1.2.3 - 2.3.4",
+        vec!["1.2.3", "2.3.4"],
+        vec!["1.2.2", "2.3.5"],
+    );
+    assert_matches("1.2 - 2.3", vec!["1.2.0", "2.3.9"], vec!["2.4.0"]);
+    assert_matches("1.2.3 - 2", vec!["2.9.9"], vec!["3.0.0"]);
+}
+
+#[test]
+fn test_or() {
+    assert_matches(
+        "^1.2 || >=3.0.0 <3.5.0",
+        vec!["1.2.0", "3.4.9"],
+        vec!["2.0.0", "3.5.0"],
+    );
+}
+
+#[test]
+fn test_prerelease() {
+    assert_matches(
+        ">1.2.3-alpha.3",
+        vec!["1.2.3-alpha.7", "3.4.5"],
+        vec!["3.4.5-alpha.9", "1.2.3-alpha.2"],
+    );
+    assert_matches(
+        "^1.2.3-beta.2",
+        vec!["1.2.3-beta.4", "1.2.3", "1.9.0"],
+        vec!["1.2.4-beta.2", "1.2.3-beta.1", "2.0.0-rc.1"],
+    );
+    assert_matches(
+        "~1.2.3-beta.2",
+        vec!["1.2.3-beta.4", "1.2.9"],
+        vec!["1.2.4-beta.2", "1.3.0"],
+    );
+}
+
+#[test]
+fn test_invalid() {
+    for req in ["abc", ">=1.2.3.4", "1.2-rc.1", ">>1.2.3", "^01.2.3"] {
+        assert!(matches!(
+            req.parse::<Requirement>().unwrap_err(),
+            VersionError::InvalidRequirement(_)
+        ));
+    }
+}
+
+#[test]
+fn test_too_large() {
+    let max = usize::MAX.to_string();
+    let test_cases = [
+        (
+            "^99999999999999999999999".to_owned(),
+            "99999999999999999999999",
+        ),
+        (
+            "1.99999999999999999999999.x".to_owned(),
+            "99999999999999999999999",
+        ),
+        (format!("^{}", max), &max),
+        (format!("~1.{}", max), &max),
+        (format!("^0.0.{}", max), &max),
+        (format!("<=1.{}", max), &max),
+        (max.to_owned(), &max),
+    ];
+    for (req, number) in test_cases {
+        assert_eq!(
+            req.parse::<Requirement>().unwrap_err(),
+            VersionError::InvalidRequirement(format!("Number too large: {}", number)),
+            "{}",
+            req
+        );
+    }
+}
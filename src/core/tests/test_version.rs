@@ -1,5 +1,6 @@
 use crate::core::{CorePart, ExtensionPart, Version, VersionError};
-use crate::tests::{v1, v2, v3};
+use crate::tests::{v1, v2, v3, v4};
+use std::cmp::Ordering;
 
 #[test]
 fn test_init() {
@@ -20,6 +21,14 @@ fn test_init() {
     assert_eq!(v3.minor, 20);
     assert_eq!(v3.patch, 10);
     assert_eq!(v3.prerelease, Some("dev.5".to_owned()));
+    assert_eq!(v3.buildmetadata, None);
+
+    let v4 = v4();
+    assert_eq!(v4.major, 1);
+    assert_eq!(v4.minor, 2);
+    assert_eq!(v4.patch, 3);
+    assert_eq!(v4.prerelease, Some("dev.5".to_owned()));
+    assert_eq!(v4.buildmetadata, Some("build.1".to_owned()));
 }
 
 #[test]
@@ -32,6 +41,20 @@ fn test_parse() {
             "30.20.10-dev.5",
             Version::with_values(30, 20, 10, Some("dev.5".to_owned())),
         ),
+        (
+            "1.2.3-alpha.5+100",
+            Version::with_values_and_buildmetadata(
+                1,
+                2,
+                3,
+                Some("alpha.5".to_owned()),
+                Some("100".to_owned()),
+            ),
+        ),
+        (
+            "30.20.10+build123",
+            Version::with_values_and_buildmetadata(30, 20, 10, None, Some("build123".to_owned())),
+        ),
     ];
     for tc in test_cases_supported {
         assert_eq!(tc.0.parse::<Version>().unwrap(), tc.1);
@@ -44,14 +67,6 @@ fn test_parse() {
             VersionError::InvalidVersion(_)
         );
     }
-
-    let unsupported_versions = vec!["1.2.3-alpha.5+100", "1.2.3-beta+b.500", "30.20.10+build123"];
-    for v in unsupported_versions {
-        matches!(
-            v.parse::<Version>().unwrap_err(),
-            VersionError::UnsupportedVersion(_)
-        );
-    }
 }
 
 #[test]
@@ -59,6 +74,12 @@ fn test_to_string() {
     assert_eq!(v1().to_string(), "0.0.0");
     assert_eq!(v2().to_string(), "1.2.3");
     assert_eq!(v3().to_string(), "30.20.10-dev.5");
+    assert_eq!(v4().to_string(), "1.2.3-dev.5+build.1");
+    assert_eq!(
+        Version::with_values_and_buildmetadata(1, 2, 3, None, Some("sha.abc".to_owned()))
+            .to_string(),
+        "1.2.3+sha.abc"
+    );
 }
 
 #[test]
@@ -83,4 +104,75 @@ fn test_getters() {
         v3.get_extension_part(&ExtensionPart::Prerelease),
         Some("dev.5".to_owned())
     );
+    assert_eq!(v3.get_extension_part(&ExtensionPart::BuildMetadata), None);
+
+    let v4 = v4();
+    assert_eq!(
+        v4.get_extension_part(&ExtensionPart::BuildMetadata),
+        Some("build.1".to_owned())
+    );
+}
+
+#[test]
+fn test_cmp_precedence() {
+    // Ref: https://semver.org/#spec-item-11
+    let ordered_versions = vec![
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-beta",
+        "1.0.0-beta.2",
+        "1.0.0-beta.11",
+        "1.0.0-rc.1",
+        "1.0.0",
+        "1.0.1",
+        "1.1.0",
+        "1.10.0",
+        "2.0.0-1",
+        "2.0.0-99999999999999999999999",
+        "2.0.0-a",
+        "2.0.0",
+    ];
+    for (i, raw_v1) in ordered_versions.iter().enumerate() {
+        let v1 = raw_v1.parse::<Version>().unwrap();
+        for (j, raw_v2) in ordered_versions.iter().enumerate() {
+            let v2 = raw_v2.parse::<Version>().unwrap();
+            assert_eq!(
+                v1.cmp_precedence(&v2),
+                i.cmp(&j),
+                "{} vs {}",
+                raw_v1,
+                raw_v2
+            );
+            assert_eq!(v1.cmp(&v2), i.cmp(&j), "{} vs {}", raw_v1, raw_v2);
+        }
+    }
+}
+
+#[test]
+fn test_cmp_buildmetadata() {
+    let v1 = "1.0.0+build.1".parse::<Version>().unwrap();
+    let v2 = "1.0.0+build.2".parse::<Version>().unwrap();
+    let v3 = "1.0.0".parse::<Version>().unwrap();
+    assert_eq!(v1.cmp_precedence(&v2), Ordering::Equal);
+    assert_eq!(v1.cmp_precedence(&v3), Ordering::Equal);
+    assert_eq!(v1.cmp(&v2), Ordering::Less);
+    assert_eq!(v3.cmp(&v1), Ordering::Less);
+    assert!(v3 < v1);
+}
+
+#[test]
+fn test_sort() {
+    let mut versions: Vec<Version> = vec!["1.0.0", "1.0.0-rc.1", "0.9.0", "1.0.0-beta.11"]
+        .into_iter()
+        .map(|v| v.parse::<Version>().unwrap())
+        .collect();
+    versions.sort();
+    assert_eq!(
+        versions
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>(),
+        vec!["0.9.0", "1.0.0-beta.11", "1.0.0-rc.1", "1.0.0"]
+    );
 }
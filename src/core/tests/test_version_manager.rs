@@ -1,4 +1,4 @@
-use crate::core::{BumpError, CorePart, ExtensionPart, Part, Version, VersionManager};
+use crate::core::{BumpContext, BumpError, CorePart, ExtensionPart, Part, Version, VersionManager};
 use crate::tests::{v1, v2, v3};
 
 struct TestCaseBump {
@@ -46,12 +46,16 @@ impl TestCaseFinalizePrerelease {
 
 fn build_version_manager(with_extension: bool) -> VersionManager<'static> {
     if with_extension {
-        VersionManager::with_extension_bump_func(Some(Box::new(|v: &Version| {
-            Ok(v.prerelease.as_ref().map_or("dev.1".to_owned(), |value| {
-                let (prefix, num) = value.split_at(4);
-                let num_inc = num.parse::<usize>().unwrap() + 1;
-                format!("{}{}", prefix, num_inc)
-            }))
+        VersionManager::with_extension_bump_func(Some(Box::new(|context: &BumpContext| {
+            Ok(context
+                .version
+                .prerelease
+                .as_ref()
+                .map_or("dev.1".to_owned(), |value| {
+                    let (prefix, num) = value.split_at(4);
+                    let num_inc = num.parse::<usize>().unwrap() + 1;
+                    format!("{}{}", prefix, num_inc)
+                }))
         })))
     } else {
         VersionManager::new()
@@ -268,6 +272,8 @@ mod test_with_extension {
         build_version_manager, get_test_cases_bump, get_test_cases_finalize_prerelease,
         get_test_cases_new_prerelease,
     };
+    use crate::core::{BumpContext, BumpError, CorePart, ExtensionPart, Part, VersionManager};
+    use crate::tests::{v2, v4};
 
     #[test]
     fn test_bump() {
@@ -295,4 +301,200 @@ mod test_with_extension {
             assert_eq!(vm.finalize_prerelease(&tc.version), tc.expected);
         }
     }
+
+    #[test]
+    fn test_bump_prerelease_invalid() {
+        let vm = VersionManager::with_extension_bump_func(Some(Box::new(|_: &BumpContext| {
+            Ok("dev_1".to_owned())
+        })));
+        assert_eq!(
+            vm.bump(&v4(), &Part::Extension(ExtensionPart::Prerelease)),
+            Err(BumpError::InvalidOperation(
+                "Invalid prerelease 'dev_1'".to_owned()
+            ))
+        );
+        assert_eq!(
+            vm.new_prerelease(&v2(), &CorePart::Minor),
+            Err(BumpError::InvalidOperation(
+                "Invalid prerelease 'dev_1'".to_owned()
+            ))
+        );
+    }
+}
+
+mod test_buildmetadata {
+    use crate::core::{
+        BuildMetadataPolicy, BumpContext, BumpError, CorePart, ExtensionPart, Part, Version,
+        VersionManager,
+    };
+    use crate::tests::{v2, v4};
+
+    fn build_version_manager(policy: BuildMetadataPolicy) -> VersionManager<'static> {
+        VersionManager::with_params(
+            Some(Box::new(|_: &BumpContext| Ok("dev.6".to_owned()))),
+            Some(Box::new(|_: &BumpContext| Ok("sha.abc123".to_owned()))),
+            policy,
+        )
+    }
+
+    #[test]
+    fn test_bump_buildmetadata() {
+        let vm = build_version_manager(BuildMetadataPolicy::Drop);
+        let part = Part::Extension(ExtensionPart::BuildMetadata);
+
+        assert_eq!(
+            vm.bump(&v2(), &part),
+            Ok(Version::with_values_and_buildmetadata(
+                1,
+                2,
+                3,
+                None,
+                Some("sha.abc123".to_owned())
+            ))
+        );
+        assert_eq!(
+            vm.bump(&v4(), &part),
+            Ok(Version::with_values_and_buildmetadata(
+                1,
+                2,
+                3,
+                Some("dev.5".to_owned()),
+                Some("sha.abc123".to_owned())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_bump_buildmetadata_missing_func() {
+        let vm = VersionManager::new();
+        assert_eq!(
+            vm.bump(&v2(), &Part::Extension(ExtensionPart::BuildMetadata)),
+            Err(BumpError::MissingBuildMetadata)
+        );
+    }
+
+    #[test]
+    fn test_bump_buildmetadata_invalid() {
+        let vm = VersionManager::with_params(
+            None,
+            Some(Box::new(|_: &BumpContext| Ok("sha_abc123".to_owned()))),
+            BuildMetadataPolicy::Drop,
+        );
+        assert_eq!(
+            vm.bump(&v2(), &Part::Extension(ExtensionPart::BuildMetadata)),
+            Err(BumpError::InvalidOperation(
+                "Invalid build metadata 'sha_abc123'".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_policy_drop() {
+        let vm = build_version_manager(BuildMetadataPolicy::Drop);
+        let v = Version::with_values_and_buildmetadata(1, 2, 3, None, Some("build.1".to_owned()));
+
+        assert_eq!(
+            vm.bump(&v, &Part::Core(CorePart::Minor)),
+            Ok(Version::with_values(1, 3, 0, None))
+        );
+        assert_eq!(
+            vm.bump(&v4(), &Part::Extension(ExtensionPart::Prerelease)),
+            Ok(Version::with_values(1, 2, 3, Some("dev.6".to_owned())))
+        );
+        assert_eq!(
+            vm.finalize_prerelease(&v4()),
+            Ok(Version::with_values(1, 2, 3, None))
+        );
+    }
+
+    #[test]
+    fn test_policy_keep() {
+        let vm = build_version_manager(BuildMetadataPolicy::Keep);
+        let v = Version::with_values_and_buildmetadata(1, 2, 3, None, Some("build.1".to_owned()));
+
+        assert_eq!(
+            vm.bump(&v, &Part::Core(CorePart::Minor)),
+            Ok(Version::with_values_and_buildmetadata(
+                1,
+                3,
+                0,
+                None,
+                Some("build.1".to_owned())
+            ))
+        );
+        assert_eq!(
+            vm.bump(&v4(), &Part::Extension(ExtensionPart::Prerelease)),
+            Ok(Version::with_values_and_buildmetadata(
+                1,
+                2,
+                3,
+                Some("dev.6".to_owned()),
+                Some("build.1".to_owned())
+            ))
+        );
+        assert_eq!(
+            vm.finalize_prerelease(&v4()),
+            Ok(Version::with_values_and_buildmetadata(
+                1,
+                2,
+                3,
+                None,
+                Some("build.1".to_owned())
+            ))
+        );
+    }
+}
+
+mod test_bump_context {
+    use crate::core::{BumpContext, CorePart, ExtensionPart, Part, Version, VersionManager};
+    use crate::tests::{v2, v4};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Bumps with a version manager whose prerelease function records the contexts it's called
+    /// with.
+    fn contexts_of<F>(bump: F) -> Vec<BumpContext>
+    where
+        F: Fn(&VersionManager),
+    {
+        let contexts = Rc::new(RefCell::new(vec![]));
+        let recorded = Rc::clone(&contexts);
+        let vm = VersionManager::with_extension_bump_func(Some(Box::new(
+            move |context: &BumpContext| {
+                recorded.borrow_mut().push(context.to_owned());
+                Ok("rc.1".to_owned())
+            },
+        )))
+        .with_last_stable_version(Some(Version::with_values(1, 2, 0, None)));
+        bump(&vm);
+        let contexts = contexts.borrow().to_vec();
+        contexts
+    }
+
+    #[test]
+    fn test_bump_context() {
+        assert_eq!(
+            contexts_of(|vm| {
+                vm.bump(&v4(), &Part::Extension(ExtensionPart::Prerelease))
+                    .unwrap();
+            }),
+            vec![BumpContext {
+                version: v4(),
+                part: Part::Extension(ExtensionPart::Prerelease),
+                last_stable_version: Some(Version::with_values(1, 2, 0, None)),
+                new_prerelease: false,
+            }]
+        );
+        assert_eq!(
+            contexts_of(|vm| {
+                vm.new_prerelease(&v2(), &CorePart::Minor).unwrap();
+            }),
+            vec![BumpContext {
+                version: Version::with_values(1, 3, 0, None),
+                part: Part::Core(CorePart::Minor),
+                last_stable_version: Some(Version::with_values(1, 2, 0, None)),
+                new_prerelease: true,
+            }]
+        );
+    }
 }
@@ -2,14 +2,16 @@ use crate::cmd::validate;
 use crate::core::{CorePart, ExtensionPart, Part, VersionError, SUPPORTED_PATTERN};
 use regex::{self, Regex};
 use serde::Serialize;
+use std::cmp::Ordering;
 use std::{fmt, str};
 
-#[derive(Debug, Eq, PartialEq, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub struct Version {
     pub major: usize,
     pub minor: usize,
     pub patch: usize,
     pub prerelease: Option<String>,
+    pub buildmetadata: Option<String>,
 }
 
 impl Default for Version {
@@ -28,12 +30,23 @@ impl Version {
         minor: usize,
         patch: usize,
         prerelease: Option<String>,
+    ) -> Self {
+        Self::with_values_and_buildmetadata(major, minor, patch, prerelease, None)
+    }
+
+    pub fn with_values_and_buildmetadata(
+        major: usize,
+        minor: usize,
+        patch: usize,
+        prerelease: Option<String>,
+        buildmetadata: Option<String>,
     ) -> Self {
         Version {
             major,
             minor,
             patch,
             prerelease,
+            buildmetadata,
         }
     }
 
@@ -48,6 +61,7 @@ impl Version {
     pub fn get_extension_part(&self, part: &ExtensionPart) -> Option<String> {
         match part {
             ExtensionPart::Prerelease => self.prerelease.to_owned(),
+            ExtensionPart::BuildMetadata => self.buildmetadata.to_owned(),
         }
     }
 
@@ -56,6 +70,48 @@ impl Version {
             .is_none()
     }
 
+    /// Compares two versions according to the semver precedence rules, i.e. ignoring the build
+    /// metadata.
+    pub fn cmp_precedence(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then(self.minor.cmp(&other.minor))
+            .then(self.patch.cmp(&other.patch))
+            .then_with(|| match (&self.prerelease, &other.prerelease) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(p1), Some(p2)) => Self::cmp_prerelease(p1, p2),
+            })
+    }
+
+    fn cmp_prerelease(p1: &str, p2: &str) -> Ordering {
+        let mut ids1 = p1.split('.');
+        let mut ids2 = p2.split('.');
+        loop {
+            match (ids1.next(), ids2.next()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(id1), Some(id2)) => match Self::cmp_identifier(id1, id2) {
+                    Ordering::Equal => continue,
+                    ord => return ord,
+                },
+            }
+        }
+    }
+
+    fn cmp_identifier(id1: &str, id2: &str) -> Ordering {
+        let is_numeric = |id: &str| id.bytes().all(|b| b.is_ascii_digit());
+        match (is_numeric(id1), is_numeric(id2)) {
+            // Numeric identifiers have no leading zeros, so the longer the greater.
+            (true, true) => id1.len().cmp(&id2.len()).then_with(|| id1.cmp(id2)),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => id1.cmp(id2),
+        }
+    }
+
     fn extract_part<T: str::FromStr>(caps: &regex::Captures, part: &Part) -> Option<T> {
         match caps.name(&part.to_string()) {
             Some(found) => found.as_str().parse::<T>().ok(),
@@ -64,22 +120,31 @@ impl Version {
     }
 }
 
+impl Ord for Version {
+    /// Orders versions by precedence, and then by build metadata so that the ordering is
+    /// consistent with equality. Use `cmp_precedence` to ignore the build metadata.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_precedence(other)
+            .then_with(|| self.buildmetadata.cmp(&other.buildmetadata))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl str::FromStr for Version {
     type Err = VersionError;
 
     fn from_str(raw_version: &str) -> Result<Self, Self::Err> {
         if !validate::validate(raw_version) {
-            Err(VersionError::InvalidVersion(format!(
-                "Invalid version: {}",
-                raw_version.to_owned()
-            )))
+            Err(VersionError::InvalidVersion(raw_version.to_owned()))
         } else {
             let re = Regex::new(SUPPORTED_PATTERN).unwrap();
             if !re.is_match(raw_version) {
-                Err(VersionError::UnsupportedVersion(format!(
-                    "Unsupported version: {}",
-                    raw_version.to_owned()
-                )))
+                Err(VersionError::UnsupportedVersion(raw_version.to_owned()))
             } else {
                 let caps = re.captures(raw_version).unwrap();
                 let major = Self::extract_part::<usize>(&caps, &Part::Core(CorePart::Major))
@@ -107,7 +172,17 @@ impl str::FromStr for Version {
                     &caps,
                     &Part::Extension(ExtensionPart::Prerelease),
                 );
-                Ok(Self::with_values(major, minor, patch, prerelease))
+                let buildmetadata = Self::extract_part::<String>(
+                    &caps,
+                    &Part::Extension(ExtensionPart::BuildMetadata),
+                );
+                Ok(Self::with_values_and_buildmetadata(
+                    major,
+                    minor,
+                    patch,
+                    prerelease,
+                    buildmetadata,
+                ))
             }
         }
     }
@@ -115,9 +190,13 @@ impl str::FromStr for Version {
 
 impl fmt::Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match &self.prerelease {
-            Some(prerel) => write!(f, "{}.{}.{}-{}", self.major, self.minor, self.patch, prerel),
-            None => write!(f, "{}.{}.{}", self.major, self.minor, self.patch),
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(prerel) = &self.prerelease {
+            write!(f, "-{}", prerel)?;
+        }
+        if let Some(buildmetadata) = &self.buildmetadata {
+            write!(f, "+{}", buildmetadata)?;
         }
+        Ok(())
     }
 }
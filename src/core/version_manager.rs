@@ -1,7 +1,22 @@
+use crate::cmd::validate;
 use crate::core::{BumpError, CorePart, ExtensionPart, Part, Version};
 
-pub trait ExtensionBumpFunc: Fn(&Version) -> Result<String, BumpError> {}
-impl<T> ExtensionBumpFunc for T where T: Fn(&Version) -> Result<String, BumpError> {}
+/// What an extension bump function is called with.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BumpContext {
+    /// The version whose extension is bumped, whose core part has already been bumped when
+    /// starting a new prerelease.
+    pub version: Version,
+    /// The part being bumped, i.e. the core one when starting a new prerelease and the extension
+    /// itself otherwise.
+    pub part: Part,
+    pub last_stable_version: Option<Version>,
+    /// Whether a new prerelease is being started rather than the current one incremented.
+    pub new_prerelease: bool,
+}
+
+pub trait ExtensionBumpFunc: Fn(&BumpContext) -> Result<String, BumpError> {}
+impl<T> ExtensionBumpFunc for T where T: Fn(&BumpContext) -> Result<String, BumpError> {}
 
 trait Bump {
     type Value;
@@ -34,27 +49,33 @@ struct ExtensionBumper<'a> {
 }
 
 impl<'a> ExtensionBumper<'a> {
-    pub fn new(bump_func: Box<dyn ExtensionBumpFunc>) -> Self {
-        Self {
-            kind: &ExtensionPart::Prerelease,
-            bump_func,
-        }
+    pub fn new(kind: &'a ExtensionPart, bump_func: Box<dyn ExtensionBumpFunc>) -> Self {
+        Self { kind, bump_func }
     }
 }
 
-impl<'a> Bump for ExtensionBumper<'a> {
-    type Value = String;
-
-    fn bump(&self, version: &Version) -> Result<Self::Value, BumpError> {
-        (self.bump_func)(version)
+impl<'a> ExtensionBumper<'a> {
+    fn bump(&self, context: &BumpContext) -> Result<String, BumpError> {
+        (self.bump_func)(context)
     }
 }
 
+/// What to do with the build metadata of a version when another part is bumped.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum BuildMetadataPolicy {
+    Keep,
+    #[default]
+    Drop,
+}
+
 pub struct VersionManager<'a> {
     core_major_bumper: CoreBumper<'a>,
     core_minor_bumper: CoreBumper<'a>,
     core_patch_bumper: CoreBumper<'a>,
-    extension_bumper: Option<ExtensionBumper<'a>>,
+    prerelease_bumper: Option<ExtensionBumper<'a>>,
+    buildmetadata_bumper: Option<ExtensionBumper<'a>>,
+    buildmetadata_policy: BuildMetadataPolicy,
+    last_stable_version: Option<Version>,
 }
 
 impl<'a> Default for VersionManager<'a> {
@@ -70,15 +91,34 @@ impl<'a> VersionManager<'a> {
 
     pub fn with_extension_bump_func(
         extension_bump_func: Option<Box<dyn ExtensionBumpFunc>>,
+    ) -> Self {
+        Self::with_params(extension_bump_func, None, BuildMetadataPolicy::default())
+    }
+
+    pub fn with_params(
+        prerelease_bump_func: Option<Box<dyn ExtensionBumpFunc>>,
+        buildmetadata_bump_func: Option<Box<dyn ExtensionBumpFunc>>,
+        buildmetadata_policy: BuildMetadataPolicy,
     ) -> Self {
         Self {
             core_major_bumper: CoreBumper::new(&CorePart::Major),
             core_minor_bumper: CoreBumper::new(&CorePart::Minor),
             core_patch_bumper: CoreBumper::new(&CorePart::Patch),
-            extension_bumper: extension_bump_func.map(|func| ExtensionBumper::new(func)),
+            prerelease_bumper: prerelease_bump_func
+                .map(|func| ExtensionBumper::new(&ExtensionPart::Prerelease, func)),
+            buildmetadata_bumper: buildmetadata_bump_func
+                .map(|func| ExtensionBumper::new(&ExtensionPart::BuildMetadata, func)),
+            buildmetadata_policy,
+            last_stable_version: None,
         }
     }
 
+    /// Sets the last stable version passed to the extension bump functions.
+    pub fn with_last_stable_version(mut self, last_stable_version: Option<Version>) -> Self {
+        self.last_stable_version = last_stable_version;
+        self
+    }
+
     pub fn bump(&self, version: &Version, part: &Part) -> Result<Version, BumpError> {
         match (part, &version.prerelease) {
             (&Part::Core(_), Some(_)) => Err(BumpError::InvalidOperation(format!(
@@ -101,10 +141,11 @@ impl<'a> VersionManager<'a> {
                 "Cannot create a new prerelease for version {}, it's already a prerelease",
                 version
             ))),
-            None => self.single_part_bump(
-                &self.single_part_bump(version, &Part::Core(part.to_owned()))?,
-                &Part::Extension(ExtensionPart::Prerelease),
-            ),
+            None => {
+                let part = Part::Core(part.to_owned());
+                let version = self.single_part_bump(version, &part)?;
+                self.prerelease_bump(self.context(&version, &part, true))
+            }
         }
     }
 
@@ -115,6 +156,7 @@ impl<'a> VersionManager<'a> {
                 minor: version.minor,
                 patch: version.patch,
                 prerelease: None,
+                buildmetadata: self.next_buildmetadata(version),
             }),
             None => Err(BumpError::InvalidOperation(format!(
                 "Cannot finalize version {}, nothing to finalize",
@@ -130,31 +172,86 @@ impl<'a> VersionManager<'a> {
                 minor: 0,
                 patch: 0,
                 prerelease: version.prerelease.to_owned(),
+                buildmetadata: self.next_buildmetadata(version),
             }),
             Part::Core(CorePart::Minor) => Ok(Version {
                 major: version.major,
                 minor: self.core_minor_bumper.bump(version)?,
                 patch: 0,
                 prerelease: version.prerelease.to_owned(),
+                buildmetadata: self.next_buildmetadata(version),
             }),
             Part::Core(CorePart::Patch) => Ok(Version {
                 major: version.major,
                 minor: version.minor,
                 patch: self.core_patch_bumper.bump(version)?,
                 prerelease: version.prerelease.to_owned(),
+                buildmetadata: self.next_buildmetadata(version),
             }),
-            Part::Extension(ExtensionPart::Prerelease) => match &self.extension_bumper {
+            Part::Extension(ExtensionPart::Prerelease) => {
+                self.prerelease_bump(self.context(version, part, false))
+            }
+            Part::Extension(ExtensionPart::BuildMetadata) => match &self.buildmetadata_bumper {
                 Some(ext_bumper) => {
-                    let prerelease = Some(ext_bumper.bump(version)?);
+                    let buildmetadata = ext_bumper.bump(&self.context(version, part, false))?;
+                    if !validate::validate_part(&buildmetadata, Some(part)) {
+                        return Err(BumpError::InvalidOperation(format!(
+                            "Invalid build metadata '{}'",
+                            buildmetadata
+                        )));
+                    }
                     Ok(Version {
                         major: version.major,
                         minor: version.minor,
                         patch: version.patch,
-                        prerelease,
+                        prerelease: version.prerelease.to_owned(),
+                        buildmetadata: Some(buildmetadata),
                     })
                 }
-                None => Err(BumpError::MissingBumpScript),
+                None => Err(BumpError::MissingBuildMetadata),
             },
         }
     }
+
+    fn prerelease_bump(&self, context: BumpContext) -> Result<Version, BumpError> {
+        match &self.prerelease_bumper {
+            Some(ext_bumper) => {
+                let prerelease = ext_bumper.bump(&context)?;
+                if !validate::validate_part(
+                    &prerelease,
+                    Some(&Part::Extension(ExtensionPart::Prerelease)),
+                ) {
+                    return Err(BumpError::InvalidOperation(format!(
+                        "Invalid prerelease '{}'",
+                        prerelease
+                    )));
+                }
+                let version = &context.version;
+                Ok(Version {
+                    major: version.major,
+                    minor: version.minor,
+                    patch: version.patch,
+                    prerelease: Some(prerelease),
+                    buildmetadata: self.next_buildmetadata(version),
+                })
+            }
+            None => Err(BumpError::MissingBumpScript),
+        }
+    }
+
+    fn context(&self, version: &Version, part: &Part, new_prerelease: bool) -> BumpContext {
+        BumpContext {
+            version: version.to_owned(),
+            part: part.to_owned(),
+            last_stable_version: self.last_stable_version.to_owned(),
+            new_prerelease,
+        }
+    }
+
+    fn next_buildmetadata(&self, version: &Version) -> Option<String> {
+        match self.buildmetadata_policy {
+            BuildMetadataPolicy::Keep => version.buildmetadata.to_owned(),
+            BuildMetadataPolicy::Drop => None,
+        }
+    }
 }
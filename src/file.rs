@@ -1,13 +1,20 @@
+mod preset;
+pub(crate) mod structured;
 #[cfg(test)]
 mod tests;
 use crate::{config, core, template};
+use similar::TextDiff;
 use std::collections::HashMap;
-use std::{fs, io};
+use std::path::{Path, PathBuf};
+use std::{fmt, fs, io};
 
 #[derive(Debug)]
 pub enum FileBumpError {
     Io(io::Error),
     NoOp(String),
+    InvalidTemplate(String),
+    UnexpectedMatches(String),
+    InvalidContent(String),
 }
 
 impl From<io::Error> for FileBumpError {
@@ -16,74 +23,377 @@ impl From<io::Error> for FileBumpError {
     }
 }
 
+impl fmt::Display for FileBumpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FileBumpError::Io(err) => write!(f, "{}", err),
+            FileBumpError::NoOp(msg) => write!(f, "{}", msg),
+            FileBumpError::InvalidTemplate(msg) => write!(f, "{}", msg),
+            FileBumpError::UnexpectedMatches(msg) => write!(f, "{}", msg),
+            FileBumpError::InvalidContent(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for FileBumpError {}
+
 impl PartialEq for FileBumpError {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (FileBumpError::Io(_), _) => false,
             (_, FileBumpError::Io(_)) => false,
             (FileBumpError::NoOp(m1), FileBumpError::NoOp(m2)) => m1 == m2,
+            (FileBumpError::InvalidTemplate(m1), FileBumpError::InvalidTemplate(m2)) => m1 == m2,
+            (FileBumpError::UnexpectedMatches(m1), FileBumpError::UnexpectedMatches(m2)) => {
+                m1 == m2
+            }
+            (FileBumpError::InvalidContent(m1), FileBumpError::InvalidContent(m2)) => m1 == m2,
+            _ => false,
         }
     }
 }
 
+/// A file whose content has to be replaced as part of a bump.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub path: String,
+    pub original_content: String,
+    pub new_content: String,
+    pub matches: usize,
+}
+
+impl FileChange {
+    pub fn unified_diff(&self) -> String {
+        TextDiff::from_lines(&self.original_content, &self.new_content)
+            .unified_diff()
+            .header(&self.path, &self.path)
+            .to_string()
+    }
+}
+
 pub fn replace_files_contents(
     current_version: &core::Version,
     new_version: &core::Version,
     last_stable_version: Option<&core::Version>,
     files: &HashMap<String, config::FileConfig>,
 ) -> Result<(), FileBumpError> {
-    let mut res: Vec<Result<String, FileBumpError>> = vec![];
-    for (file_path, file_config) in files {
+    let changes = compute_files_contents(current_version, new_version, last_stable_version, files)?;
+    write_files_contents(&changes)
+}
+
+/// Computes the new content of all the files without writing them, failing if any of them
+/// wouldn't change.
+pub fn compute_files_contents(
+    current_version: &core::Version,
+    new_version: &core::Version,
+    last_stable_version: Option<&core::Version>,
+    files: &HashMap<String, config::FileConfig>,
+) -> Result<Vec<FileChange>, FileBumpError> {
+    let mut replacements_by_path: Vec<(String, Vec<Replacement>)> = vec![];
+    let mut file_paths: Vec<&String> = files.keys().collect();
+    file_paths.sort_unstable();
+    for file_path in file_paths {
+        let file_config = &files[file_path];
         let is_stable = new_version.is_stable();
         let stable_only = file_config.stable_only.unwrap_or(false);
         if is_stable || !stable_only {
-            res.push(replace_file_content(
-                current_version,
-                new_version,
-                last_stable_version,
-                file_config
-                    .search
-                    .as_ref()
-                    .unwrap_or(&"{current_version}".to_owned()),
-                file_config
-                    .replace
-                    .as_ref()
-                    .unwrap_or(&"{new_version}".to_owned()),
-                file_path,
-            ));
+            for replacement in Replacement::from_file_config(file_path, file_config)? {
+                match replacements_by_path
+                    .iter_mut()
+                    .find(|(path, _)| *path == replacement.path)
+                {
+                    Some((_, replacements)) => replacements.push(replacement),
+                    None => {
+                        replacements_by_path.push((replacement.path.to_owned(), vec![replacement]))
+                    }
+                }
+            }
+        }
+    }
+
+    let mut changes = vec![];
+    for (file_path, replacements) in replacements_by_path {
+        if let Some(change) = compute_file_content(
+            current_version,
+            new_version,
+            last_stable_version,
+            &replacements,
+            &file_path,
+        )? {
+            changes.push(change);
+        }
+    }
+    Ok(changes)
+}
+
+/// Writes all the changes or none of them.
+///
+/// Every new content is first written to a temporary file next to the target one, and then all
+/// the temporary files are renamed over the targets. If any step fails, the temporary files are
+/// removed and the files already renamed are restored to their original content. Symbolic links
+/// are resolved so that the files they point to are written rather than replaced.
+pub fn write_files_contents(changes: &[FileChange]) -> Result<(), FileBumpError> {
+    let targets: Vec<PathBuf> = changes
+        .iter()
+        .map(|change| {
+            fs::canonicalize(&change.path).unwrap_or_else(|_| PathBuf::from(&change.path))
+        })
+        .collect();
+    let mut tmp_paths = vec![];
+    for (change, target) in changes.iter().zip(&targets) {
+        match write_tmp_file(target, &change.new_content) {
+            Ok(tmp_path) => tmp_paths.push(tmp_path),
+            Err(err) => {
+                remove_tmp_files(&tmp_paths);
+                return Err(FileBumpError::from(err));
+            }
         }
     }
 
-    // TODO: In case of an error in one of the files, they should all be reverted.
-    res.into_iter()
-        .collect::<Result<Vec<String>, FileBumpError>>()
-        .map(|_| ())
+    for (i, (target, tmp_path)) in targets.iter().zip(&tmp_paths).enumerate() {
+        if let Err(err) = fs::rename(tmp_path, target) {
+            remove_tmp_files(&tmp_paths[i..]);
+            for (written, written_target) in changes[..i].iter().zip(&targets) {
+                // Best effort: the original error is more relevant than a failed restore.
+                let _ = write_tmp_file(written_target, &written.original_content)
+                    .and_then(|restore_path| fs::rename(restore_path, written_target));
+            }
+            return Err(FileBumpError::from(err));
+        }
+    }
+    Ok(())
+}
+
+fn write_tmp_file(path: &Path, content: &str) -> Result<PathBuf, io::Error> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| io::Error::other(format!("Invalid file path '{}'", path.display())))?;
+    let tmp_path = path.with_file_name(format!(".{}.semver-tmp", file_name));
+    fs::write(&tmp_path, content)?;
+    if let Err(err) = fs::metadata(path)
+        .and_then(|metadata| fs::set_permissions(&tmp_path, metadata.permissions()))
+    {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(err);
+    }
+    Ok(tmp_path)
+}
+
+fn remove_tmp_files(tmp_paths: &[PathBuf]) {
+    for tmp_path in tmp_paths {
+        let _ = fs::remove_file(tmp_path);
+    }
+}
+
+/// A file configuration to apply to a file, either as configured or as derived from a preset.
+#[derive(Debug, Clone, PartialEq)]
+struct Replacement {
+    path: String,
+    file_config: config::FileConfig,
+    /// Whether the replacement is skipped, rather than failing, when either the file or the key
+    /// doesn't exist.
+    optional: bool,
+}
+
+impl Replacement {
+    fn from_file_config(
+        file_path: &str,
+        file_config: &config::FileConfig,
+    ) -> Result<Vec<Replacement>, FileBumpError> {
+        match file_config.preset {
+            Some(file_preset) => preset::expand(file_path, file_preset, file_config),
+            None => Ok(vec![Replacement {
+                path: file_path.to_owned(),
+                file_config: file_config.clone(),
+                optional: false,
+            }]),
+        }
+    }
 }
 
-fn replace_file_content(
+/// Applies all the replacements of a file one after the other, returning `None` if they are all
+/// optional and none of them applies.
+fn compute_file_content(
     current_version: &core::Version,
     new_version: &core::Version,
     last_stable_version: Option<&core::Version>,
-    search: &str,
-    replace: &str,
+    replacements: &[Replacement],
     file_path: &str,
-) -> Result<String, FileBumpError> {
+) -> Result<Option<FileChange>, FileBumpError> {
+    let all_optional = replacements.iter().all(|replacement| replacement.optional);
+    if all_optional && !Path::new(file_path).exists() {
+        return Ok(None);
+    }
     let content = fs::read_to_string(file_path)?;
     let context = template::Context::with_versions(
         current_version.to_string(),
         new_version.to_string(),
         last_stable_version
             .map(|v| v.to_string())
-            .unwrap_or_else(|| "".to_string()),
+            .unwrap_or_default(),
     );
-    let replaced_content = template::replace_content(&content, search, replace, &context).unwrap();
-    if content == replaced_content {
+
+    let mut replaced_content = content.to_owned();
+    let mut matches = 0;
+    for replacement in replacements {
+        let file_config = &replacement.file_config;
+        let search = file_config.search.as_deref().unwrap_or("{current_version}");
+        let replace = file_config.replace.as_deref().unwrap_or("{new_version}");
+        let (replaced, found) = match &file_config.format {
+            Some(format) => replace_value(
+                &replaced_content,
+                format,
+                replacement,
+                search,
+                replace,
+                &context,
+            )?,
+            None => replace_text(
+                &replaced_content,
+                file_config,
+                search,
+                replace,
+                &context,
+                file_path,
+            )?,
+        };
+        replaced_content = replaced;
+        matches += found;
+    }
+
+    if content != replaced_content {
+        Ok(Some(FileChange {
+            path: file_path.to_owned(),
+            original_content: content,
+            new_content: replaced_content,
+            matches,
+        }))
+    } else if all_optional {
+        Ok(None)
+    } else {
         Err(FileBumpError::NoOp(format!(
             "Nothing changed in file '{}'",
             file_path
         )))
+    }
+}
+
+fn replace_text(
+    content: &str,
+    file_config: &config::FileConfig,
+    search: &str,
+    replace: &str,
+    context: &template::Context,
+    file_path: &str,
+) -> Result<(String, usize), FileBumpError> {
+    let limit = file_config.first_only.unwrap_or(false).then_some(1);
+    let (start, end) = match file_config.lines {
+        Some((first_line, last_line)) => lines_span(content, first_line, last_line),
+        None => (0, content.len()),
+    };
+    let scope = &content[start..end];
+    let replaced = if file_config.search_regex.unwrap_or(false) {
+        template::replace_regex_with_count(scope, search, replace, context, limit)
     } else {
-        fs::write(file_path, &replaced_content)?;
-        Ok(replaced_content)
+        template::replace_content_with_count(scope, search, replace, context, limit)
+    };
+    let (replaced_scope, found) = replaced.map_err(|err| invalid_template(err, file_path))?;
+    check_matches(file_config, found, file_path)?;
+
+    let replaced_content = format!("{}{}{}", &content[..start], replaced_scope, &content[end..]);
+    Ok((
+        replaced_content,
+        limit.map_or(found, |limit| found.min(limit)),
+    ))
+}
+
+/// Replaces the value at the `key` of the file configuration, which must be equal to `search`.
+fn replace_value(
+    content: &str,
+    format: &config::FileFormat,
+    replacement: &Replacement,
+    search: &str,
+    replace: &str,
+    context: &template::Context,
+) -> Result<(String, usize), FileBumpError> {
+    let file_path = &replacement.path;
+    let key = replacement.file_config.key.as_deref().ok_or_else(|| {
+        FileBumpError::InvalidContent(format!("Missing key for file '{}'", file_path))
+    })?;
+    let invalid_content = |err: structured::ValueError| {
+        FileBumpError::InvalidContent(format!("{} in file '{}'", err, file_path))
+    };
+
+    let expected_value =
+        template::render(search, context).map_err(|err| invalid_template(err, file_path))?;
+    let value = match structured::get_value(content, format, key) {
+        Err(structured::ValueError::KeyNotFound(_)) if replacement.optional => {
+            return Ok((content.to_owned(), 0))
+        }
+        value => value.map_err(invalid_content)?,
+    };
+    if value != expected_value {
+        return Err(FileBumpError::UnexpectedMatches(format!(
+            "Found '{}' at key '{}' in file '{}' while expecting '{}'",
+            value, key, file_path, expected_value
+        )));
+    }
+
+    let new_value =
+        template::render(replace, context).map_err(|err| invalid_template(err, file_path))?;
+    let replaced_content =
+        structured::set_value(content, format, key, &new_value).map_err(invalid_content)?;
+    Ok((replaced_content, 1))
+}
+
+fn invalid_template(err: Box<dyn std::error::Error>, file_path: &str) -> FileBumpError {
+    FileBumpError::InvalidTemplate(format!(
+        "Invalid search or replace for file '{}': {}",
+        file_path, err
+    ))
+}
+
+/// Returns the byte offsets spanning the lines from `first_line` to `last_line`, 1-based and
+/// inclusive, clamped to the lines of the content.
+fn lines_span(content: &str, first_line: usize, last_line: usize) -> (usize, usize) {
+    let mut start = content.len();
+    let mut end = content.len();
+    let mut offset = 0;
+    for (i, line) in content.split_inclusive('\n').enumerate() {
+        let line_number = i + 1;
+        if line_number == first_line.max(1) {
+            start = offset;
+        }
+        offset += line.len();
+        if line_number == last_line {
+            end = offset;
+            break;
+        }
+    }
+    (start, end.max(start))
+}
+
+fn check_matches(
+    file_config: &config::FileConfig,
+    found: usize,
+    file_path: &str,
+) -> Result<(), FileBumpError> {
+    if let Some(expected_count) = file_config.expected_count {
+        if found != expected_count {
+            return Err(FileBumpError::UnexpectedMatches(format!(
+                "Found {} occurrences in file '{}' while expecting exactly {}",
+                found, file_path, expected_count
+            )));
+        }
+    }
+    if let Some(max_replacements) = file_config.max_replacements {
+        if found > max_replacements {
+            return Err(FileBumpError::UnexpectedMatches(format!(
+                "Found {} occurrences in file '{}' while expecting at most {}",
+                found, file_path, max_replacements
+            )));
+        }
     }
+    Ok(())
 }
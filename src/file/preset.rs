@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests;
+use super::{structured, FileBumpError, Replacement};
+use crate::config::{FileConfig, FileFormat, Preset};
+use std::fs;
+use std::path::Path;
+
+/// Expands a file configured with a preset into the replacements of the file itself and of its
+/// companion files, if any.
+///
+/// The options explicitly set in the file configuration take precedence over the ones of the
+/// preset. Companion files are next to the file and are skipped when they don't exist.
+pub fn expand(
+    file_path: &str,
+    preset: Preset,
+    file_config: &FileConfig,
+) -> Result<Vec<Replacement>, FileBumpError> {
+    let main = |preset_config: FileConfig| Replacement {
+        path: file_path.to_owned(),
+        file_config: merge(preset_config, file_config),
+        optional: false,
+    };
+    let companion = |file_name: &str, file_config: FileConfig| Replacement {
+        path: Path::new(file_path)
+            .with_file_name(file_name)
+            .to_string_lossy()
+            .into_owned(),
+        file_config,
+        optional: true,
+    };
+
+    let replacements = match preset {
+        Preset::Cargo => {
+            let mut replacements = vec![main(FileConfig::with_key(
+                FileFormat::Toml,
+                "package.version".to_owned(),
+            ))];
+            if let Some(name) = read_value(file_path, FileFormat::Toml, "package.name")? {
+                replacements.push(companion(
+                    "Cargo.lock",
+                    FileConfig {
+                        first_only: Some(true),
+                        ..FileConfig::with_pattern(
+                            format!("name = \"{}\"\nversion = \"{{current_version}}\"", name),
+                            format!("name = \"{}\"\nversion = \"{{new_version}}\"", name),
+                        )
+                    },
+                ));
+            }
+            replacements
+        }
+        Preset::Npm => vec![
+            main(FileConfig::with_key(FileFormat::Json, "version".to_owned())),
+            companion(
+                "package-lock.json",
+                FileConfig::with_key(FileFormat::Json, "version".to_owned()),
+            ),
+            // The root package of lockfiles from version 2 onwards, whose key is empty
+            companion(
+                "package-lock.json",
+                FileConfig::with_key(FileFormat::Json, "packages..version".to_owned()),
+            ),
+        ],
+        Preset::Pyproject => {
+            // Poetry keeps the version in its own section rather than in the standard one
+            let is_poetry = read_value(file_path, FileFormat::Toml, "project.version")?.is_none()
+                && read_value(file_path, FileFormat::Toml, "tool.poetry.version")?.is_some();
+            let key = if is_poetry {
+                "tool.poetry.version"
+            } else {
+                "project.version"
+            };
+            vec![main(FileConfig::with_key(FileFormat::Toml, key.to_owned()))]
+        }
+        Preset::SetupCfg => vec![main(FileConfig {
+            expected_count: Some(1),
+            ..FileConfig::with_regex(
+                r"(?m)(^\[metadata\][^\[]*?^version[ \t]*=[ \t]*){current_version}([ \t]*\r?)$"
+                    .to_owned(),
+                "${1}{new_version}${2}".to_owned(),
+            )
+        })],
+        Preset::HelmChart => vec![main(FileConfig::with_key(
+            FileFormat::Yaml,
+            "version".to_owned(),
+        ))],
+        // The version of the project is the first one unless there's a parent with the same one
+        Preset::MavenPom => vec![main(FileConfig {
+            first_only: Some(true),
+            ..FileConfig::with_pattern(
+                "<version>{current_version}</version>".to_owned(),
+                "<version>{new_version}</version>".to_owned(),
+            )
+        })],
+    };
+    Ok(replacements)
+}
+
+/// Reads the string value at `key`, returning `None` if the file cannot be parsed or the value
+/// is missing.
+fn read_value(
+    file_path: &str,
+    format: FileFormat,
+    key: &str,
+) -> Result<Option<String>, FileBumpError> {
+    let content = fs::read_to_string(file_path)?;
+    Ok(structured::get_value(&content, &format, key).ok())
+}
+
+fn merge(preset_config: FileConfig, file_config: &FileConfig) -> FileConfig {
+    let file_config = file_config.clone();
+    FileConfig {
+        search: file_config.search.or(preset_config.search),
+        replace: file_config.replace.or(preset_config.replace),
+        stable_only: file_config.stable_only.or(preset_config.stable_only),
+        search_regex: file_config.search_regex.or(preset_config.search_regex),
+        max_replacements: file_config
+            .max_replacements
+            .or(preset_config.max_replacements),
+        expected_count: file_config.expected_count.or(preset_config.expected_count),
+        first_only: file_config.first_only.or(preset_config.first_only),
+        lines: file_config.lines.or(preset_config.lines),
+        format: file_config.format.or(preset_config.format),
+        key: file_config.key.or(preset_config.key),
+        preset: None,
+    }
+}
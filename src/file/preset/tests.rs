@@ -0,0 +1,279 @@
+use crate::config::{FileConfig, Preset};
+use crate::core::Version;
+use crate::file::{compute_files_contents, FileBumpError};
+use std::collections::HashMap;
+use std::fs;
+
+const TEST_DIR_BASE_NAME: &str = "./__";
+
+struct TestCase {
+    preset: Preset,
+    /// The file with the preset and its content before and after the bump
+    file: (&'static str, &'static str, &'static str),
+    /// Companion files with their content before and after the bump
+    companions: Vec<(&'static str, &'static str, &'static str)>,
+}
+
+fn assert_test_case(test_func_name: &str, test_case: TestCase) {
+    let test_dir_name = format!("{}{}", TEST_DIR_BASE_NAME, test_func_name);
+    fs::create_dir_all(&test_dir_name).unwrap();
+    let path = |file_name: &str| format!("{}/{}", test_dir_name, file_name);
+    let (file_name, content, new_content) = test_case.file;
+    fs::write(path(file_name), content).unwrap();
+    for (companion_name, companion_content, _) in &test_case.companions {
+        fs::write(path(companion_name), companion_content).unwrap();
+    }
+
+    let files = HashMap::from([(path(file_name), FileConfig::with_preset(test_case.preset))]);
+    let changes = compute_files_contents(
+        &Version::with_values(1, 2, 3, None),
+        &Version::with_values(1, 3, 0, None),
+        None,
+        &files,
+    );
+    fs::remove_dir_all(&test_dir_name).unwrap();
+
+    let changes: HashMap<String, String> = changes
+        .unwrap()
+        .into_iter()
+        .map(|change| (change.path, change.new_content))
+        .collect();
+    let mut expected = HashMap::from([(path(file_name), new_content.to_owned())]);
+    for (companion_name, companion_content, new_companion_content) in test_case.companions {
+        if companion_content != new_companion_content {
+            expected.insert(path(companion_name), new_companion_content.to_owned());
+        }
+    }
+    assert_eq!(changes, expected);
+}
+
+#[test]
+fn test_cargo() {
+    assert_test_case(
+        "test_preset_cargo",
+        TestCase {
+            preset: Preset::Cargo,
+            file: (
+                "Cargo.toml",
+                "[package]\nname = \"app\"\nversion = \"1.2.3\"\n\n[dependencies]\nlib = \"1.2.3\"\n",
+                "[package]\nname = \"app\"\nversion = \"1.3.0\"\n\n[dependencies]\nlib = \"1.2.3\"\n",
+            ),
+            companions: vec![(
+                "Cargo.lock",
+                "[[package]]\nname = \"app\"\nversion = \"1.2.3\"\n\n[[package]]\nname = \"lib\"\nversion = \"1.2.3\"\n",
+                "[[package]]\nname = \"app\"\nversion = \"1.3.0\"\n\n[[package]]\nname = \"lib\"\nversion = \"1.2.3\"\n",
+            )],
+        },
+    );
+}
+
+#[test]
+fn test_cargo_without_lockfile() {
+    assert_test_case(
+        "test_preset_cargo_without_lockfile",
+        TestCase {
+            preset: Preset::Cargo,
+            file: (
+                "Cargo.toml",
+                "[package]\nname = \"app\"\nversion = \"1.2.3\"\n",
+                "[package]\nname = \"app\"\nversion = \"1.3.0\"\n",
+            ),
+            companions: vec![],
+        },
+    );
+}
+
+#[test]
+fn test_npm() {
+    assert_test_case(
+        "test_preset_npm",
+        TestCase {
+            preset: Preset::Npm,
+            file: (
+                "package.json",
+                "{\n  \"name\": \"app\",\n  \"version\": \"1.2.3\",\n  \"dependencies\": {\"lib\": \"1.2.3\"}\n}\n",
+                "{\n  \"name\": \"app\",\n  \"version\": \"1.3.0\",\n  \"dependencies\": {\"lib\": \"1.2.3\"}\n}\n",
+            ),
+            companions: vec![(
+                "package-lock.json",
+                r#"{
+  "name": "app",
+  "version": "1.2.3",
+  "lockfileVersion": 3,
+  "packages": {
+    "": {"name": "app", "version": "1.2.3"},
+    "node_modules/lib": {"version": "1.2.3"}
+  }
+}
+"#,
+                r#"{
+  "name": "app",
+  "version": "1.3.0",
+  "lockfileVersion": 3,
+  "packages": {
+    "": {"name": "app", "version": "1.3.0"},
+    "node_modules/lib": {"version": "1.2.3"}
+  }
+}
+"#,
+            )],
+        },
+    );
+}
+
+#[test]
+fn test_npm_lockfile_v1() {
+    assert_test_case(
+        "test_preset_npm_lockfile_v1",
+        TestCase {
+            preset: Preset::Npm,
+            file: (
+                "package.json",
+                r#"{"name": "app", "version": "1.2.3"}"#,
+                r#"{"name": "app", "version": "1.3.0"}"#,
+            ),
+            companions: vec![(
+                "package-lock.json",
+                r#"{"name": "app", "version": "1.2.3", "lockfileVersion": 1}"#,
+                r#"{"name": "app", "version": "1.3.0", "lockfileVersion": 1}"#,
+            )],
+        },
+    );
+}
+
+#[test]
+fn test_pyproject() {
+    assert_test_case(
+        "test_preset_pyproject",
+        TestCase {
+            preset: Preset::Pyproject,
+            file: (
+                "pyproject.toml",
+                "[project]\nname = \"app\"\nversion = \"1.2.3\"\n",
+                "[project]\nname = \"app\"\nversion = \"1.3.0\"\n",
+            ),
+            companions: vec![],
+        },
+    );
+}
+
+#[test]
+fn test_pyproject_poetry() {
+    assert_test_case(
+        "test_preset_pyproject_poetry",
+        TestCase {
+            preset: Preset::Pyproject,
+            file: (
+                "pyproject.toml",
+                "[tool.poetry]\nname = \"app\"\nversion = \"1.2.3\"\n",
+                "[tool.poetry]\nname = \"app\"\nversion = \"1.3.0\"\n",
+            ),
+            companions: vec![],
+        },
+    );
+}
+
+#[test]
+fn test_setup_cfg() {
+    assert_test_case(
+        "test_preset_setup_cfg",
+        TestCase {
+            preset: Preset::SetupCfg,
+            file: (
+                "setup.cfg",
+                "[metadata]\nname = app\nversion = 1.2.3\n\n[options]\ninstall_requires =\n    lib>=1.2.3\n",
+                "[metadata]\nname = app\nversion = 1.3.0\n\n[options]\ninstall_requires =\n    lib>=1.2.3\n",
+            ),
+            companions: vec![],
+        },
+    );
+}
+
+#[test]
+fn test_helm_chart() {
+    assert_test_case(
+        "test_preset_helm_chart",
+        TestCase {
+            preset: Preset::HelmChart,
+            file: (
+                "Chart.yaml",
+                "apiVersion: v2\nname: app\nversion: 1.2.3\nappVersion: \"1.2.3\"\n",
+                "apiVersion: v2\nname: app\nversion: 1.3.0\nappVersion: \"1.2.3\"\n",
+            ),
+            companions: vec![],
+        },
+    );
+}
+
+#[test]
+fn test_maven_pom() {
+    assert_test_case(
+        "test_preset_maven_pom",
+        TestCase {
+            preset: Preset::MavenPom,
+            file: (
+                "pom.xml",
+                r#"<project>
+  <parent>
+    <artifactId>parent</artifactId>
+    <version>2.0.0</version>
+  </parent>
+  <artifactId>app</artifactId>
+  <version>1.2.3</version>
+  <dependencies>
+    <dependency>
+      <artifactId>lib</artifactId>
+      <version>1.2.3</version>
+    </dependency>
+  </dependencies>
+</project>
+"#,
+                r#"<project>
+  <parent>
+    <artifactId>parent</artifactId>
+    <version>2.0.0</version>
+  </parent>
+  <artifactId>app</artifactId>
+  <version>1.3.0</version>
+  <dependencies>
+    <dependency>
+      <artifactId>lib</artifactId>
+      <version>1.2.3</version>
+    </dependency>
+  </dependencies>
+</project>
+"#,
+            ),
+            companions: vec![],
+        },
+    );
+}
+
+#[test]
+fn test_overridden_options() {
+    let test_dir_name = format!("{}test_preset_overridden_options", TEST_DIR_BASE_NAME);
+    fs::create_dir_all(&test_dir_name).unwrap();
+    let file_path = format!("{}/Chart.yaml", test_dir_name);
+    fs::write(&file_path, "version: 0.1.0\nappVersion: v1.2.3\n").unwrap();
+    let files = HashMap::from([(
+        file_path.to_owned(),
+        FileConfig {
+            key: Some("appVersion".to_owned()),
+            search: Some("v{current_version}".to_owned()),
+            replace: Some("v{new_version}".to_owned()),
+            ..FileConfig::with_preset(Preset::HelmChart)
+        },
+    )]);
+    let changes = compute_files_contents(
+        &Version::with_values(1, 2, 3, None),
+        &Version::with_values(1, 3, 0, None),
+        None,
+        &files,
+    );
+    fs::remove_dir_all(&test_dir_name).unwrap();
+
+    assert_eq!(
+        changes.map(|changes| changes[0].new_content.to_owned()),
+        Ok::<_, FileBumpError>("version: 0.1.0\nappVersion: v1.3.0\n".to_owned())
+    );
+}
@@ -0,0 +1,361 @@
+#[cfg(test)]
+mod tests;
+use crate::config::FileFormat;
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Debug, PartialEq)]
+pub enum ValueError {
+    Parse(String),
+    KeyNotFound(String),
+    NotAString(String),
+}
+
+impl fmt::Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValueError::Parse(msg) => write!(f, "Cannot parse content: {}", msg),
+            ValueError::KeyNotFound(key) => write!(f, "Key '{}' not found", key),
+            ValueError::NotAString(key) => write!(f, "Value of key '{}' is not a string", key),
+        }
+    }
+}
+
+/// Returns the string value at the dotted `key` path of `content`.
+pub fn get_value(content: &str, format: &FileFormat, key: &str) -> Result<String, ValueError> {
+    match format {
+        FileFormat::Toml => toml_item(&parse_toml(content)?, key)?
+            .as_str()
+            .map(|value| value.to_owned())
+            .ok_or_else(|| ValueError::NotAString(key.to_owned())),
+        FileFormat::Json => {
+            let span = json_span(content, key)?;
+            serde_json::from_str(&content[span]).map_err(|_| ValueError::NotAString(key.to_owned()))
+        }
+        FileFormat::Yaml => {
+            let span = yaml_span(content, key)?;
+            Ok(yaml_unquote(&content[span]))
+        }
+    }
+}
+
+/// Sets the string value at the dotted `key` path of `content`, leaving the rest of the content
+/// untouched.
+pub fn set_value(
+    content: &str,
+    format: &FileFormat,
+    key: &str,
+    value: &str,
+) -> Result<String, ValueError> {
+    let (span, new_value) = match format {
+        FileFormat::Toml => {
+            let mut doc = parse_toml(content)?;
+            let item = toml_item_mut(&mut doc, key)?;
+            let old_value = item
+                .as_value_mut()
+                .filter(|old_value| old_value.is_str())
+                .ok_or_else(|| ValueError::NotAString(key.to_owned()))?;
+            let decor = old_value.decor().clone();
+            *old_value = toml_edit::Value::from(value);
+            *old_value.decor_mut() = decor;
+            return Ok(doc.to_string());
+        }
+        FileFormat::Json => {
+            let span = json_span(content, key)?;
+            if !content[span.clone()].starts_with('"') {
+                return Err(ValueError::NotAString(key.to_owned()));
+            }
+            (span, serde_json::to_string(value).unwrap())
+        }
+        FileFormat::Yaml => {
+            let span = yaml_span(content, key)?;
+            let new_value = match content[span.clone()].chars().next() {
+                Some('\'') => format!("'{}'", value.replace('\'', "''")),
+                Some('"') => serde_json::to_string(value).unwrap(),
+                _ => value.to_owned(),
+            };
+            (span, new_value)
+        }
+    };
+    Ok(format!(
+        "{}{}{}",
+        &content[..span.start],
+        new_value,
+        &content[span.end..]
+    ))
+}
+
+/// Like `set_value`, but if the key is missing it's inserted into its parent. Insertion is only
+/// supported by TOML and JSON.
+pub fn insert_value(
+    content: &str,
+    format: &FileFormat,
+    key: &str,
+    value: &str,
+) -> Result<String, ValueError> {
+    match set_value(content, format, key, value) {
+        Err(ValueError::KeyNotFound(_)) => {}
+        result => return result,
+    }
+    let (parent_key, last_segment) = match key.rsplit_once('.') {
+        Some((parent_key, last_segment)) => (Some(parent_key), last_segment),
+        None => (None, key),
+    };
+    match format {
+        FileFormat::Toml => {
+            let mut doc = parse_toml(content)?;
+            let parent = match parent_key {
+                Some(parent_key) => toml_item_mut(&mut doc, parent_key)?,
+                None => doc.as_item_mut(),
+            };
+            if !parent.is_table_like() {
+                return Err(ValueError::KeyNotFound(key.to_owned()));
+            }
+            parent[last_segment] = toml_edit::value(value);
+            Ok(doc.to_string())
+        }
+        FileFormat::Json => {
+            let span = match parent_key {
+                Some(parent_key) => json_span(content, parent_key)?,
+                None => {
+                    let start = content.len() - content.trim_start().len();
+                    start..content.trim_end().len()
+                }
+            };
+            if !content[span.clone()].starts_with('{') {
+                return Err(ValueError::KeyNotFound(key.to_owned()));
+            }
+            let members = &content[span.start + 1..];
+            let first_member = members.trim_start();
+            let indentation = &members[..members.len() - first_member.len()];
+            let member = format!(
+                "{}: {}",
+                serde_json::to_string(last_segment).unwrap(),
+                serde_json::to_string(value).unwrap()
+            );
+            // The new member is the first one, using the same indentation of the others
+            let inserted = if first_member.starts_with('}') {
+                member
+            } else if indentation.is_empty() {
+                format!("{}, ", member)
+            } else {
+                format!("{}{},", indentation, member)
+            };
+            Ok(format!(
+                "{}{}{}",
+                &content[..span.start + 1],
+                inserted,
+                members
+            ))
+        }
+        FileFormat::Yaml => Err(ValueError::KeyNotFound(key.to_owned())),
+    }
+}
+
+fn parse_toml(content: &str) -> Result<toml_edit::Document, ValueError> {
+    content
+        .parse::<toml_edit::Document>()
+        .map_err(|err| ValueError::Parse(err.to_string()))
+}
+
+fn toml_item<'a>(
+    doc: &'a toml_edit::Document,
+    key: &str,
+) -> Result<&'a toml_edit::Item, ValueError> {
+    key.split('.').try_fold(doc.as_item(), |item, segment| {
+        item.get(segment)
+            .filter(|item| !item.is_none())
+            .ok_or_else(|| ValueError::KeyNotFound(key.to_owned()))
+    })
+}
+
+fn toml_item_mut<'a>(
+    doc: &'a mut toml_edit::Document,
+    key: &str,
+) -> Result<&'a mut toml_edit::Item, ValueError> {
+    // Indexing a table inserts an empty item for a missing key
+    key.split('.').try_fold(doc.as_item_mut(), |item, segment| {
+        match item.get_mut(segment) {
+            Some(item) if !item.is_none() => Ok(item),
+            _ => Err(ValueError::KeyNotFound(key.to_owned())),
+        }
+    })
+}
+
+/// Returns the byte range of the raw value at the dotted `key` path of a JSON document.
+fn json_span(content: &str, key: &str) -> Result<Range<usize>, ValueError> {
+    serde_json::from_str::<serde_json::Value>(content)
+        .map_err(|err| ValueError::Parse(err.to_string()))?;
+    let mut scanner = JsonScanner {
+        bytes: content.as_bytes(),
+        pos: 0,
+    };
+    let segments: Vec<&str> = key.split('.').collect();
+    scanner
+        .find(&segments)
+        .ok_or_else(|| ValueError::KeyNotFound(key.to_owned()))
+}
+
+/// Minimal scanner over a JSON document already known to be valid.
+struct JsonScanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> JsonScanner<'a> {
+    fn find(&mut self, segments: &[&str]) -> Option<Range<usize>> {
+        self.skip_whitespaces();
+        let (segment, rest) = match segments.split_first() {
+            Some(split) => split,
+            None => {
+                let start = self.pos;
+                self.skip_value();
+                return Some(start..self.pos);
+            }
+        };
+        if self.bytes[self.pos] != b'{' {
+            return None;
+        }
+        self.pos += 1;
+        loop {
+            self.skip_whitespaces();
+            if self.bytes[self.pos] == b'}' {
+                return None;
+            }
+            let key_start = self.pos;
+            self.skip_string();
+            let key: String = serde_json::from_slice(&self.bytes[key_start..self.pos]).ok()?;
+            self.skip_whitespaces();
+            // Skips the `:`
+            self.pos += 1;
+            if key == *segment {
+                return self.find(rest);
+            }
+            self.skip_whitespaces();
+            self.skip_value();
+            self.skip_whitespaces();
+            if self.bytes[self.pos] == b'}' {
+                return None;
+            }
+            // Skips the `,`
+            self.pos += 1;
+        }
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while self.pos < self.bytes.len() && self.bytes[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_string(&mut self) {
+        self.pos += 1;
+        while self.bytes[self.pos] != b'"' {
+            self.pos += if self.bytes[self.pos] == b'\\' { 2 } else { 1 };
+        }
+        self.pos += 1;
+    }
+
+    fn skip_value(&mut self) {
+        let mut depth = 0;
+        while self.pos < self.bytes.len() {
+            match self.bytes[self.pos] {
+                b'"' => {
+                    self.skip_string();
+                    if depth == 0 {
+                        return;
+                    }
+                    continue;
+                }
+                b'{' | b'[' => depth += 1,
+                b'}' | b']' if depth == 0 => return,
+                b'}' | b']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.pos += 1;
+                        return;
+                    }
+                }
+                b',' if depth == 0 => return,
+                byte if byte.is_ascii_whitespace() && depth == 0 => return,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+}
+
+/// Returns the byte range of the scalar at the dotted `key` path of a YAML document.
+///
+/// Only block mappings whose values are single-line scalars are supported, which is what files
+/// like `Chart.yaml` use for versions.
+fn yaml_span(content: &str, key: &str) -> Result<Range<usize>, ValueError> {
+    let segments: Vec<&str> = key.split('.').collect();
+    let mut depth = 0;
+    let mut parent_indent: Option<usize> = None;
+    let mut level_indent: Option<usize> = None;
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let trimmed = line.trim_start();
+        if trimmed.trim().is_empty() || trimmed.starts_with('#') || trimmed.starts_with("---") {
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+        if parent_indent.is_some_and(|parent_indent| indent <= parent_indent) {
+            break;
+        }
+        if *level_indent.get_or_insert(indent) != indent {
+            continue;
+        }
+        let (line_key, value_start) = match yaml_key(trimmed) {
+            Some(found) => found,
+            None => continue,
+        };
+        if line_key != segments[depth] {
+            continue;
+        }
+        if depth + 1 < segments.len() {
+            depth += 1;
+            parent_indent = Some(indent);
+            level_indent = None;
+            continue;
+        }
+        let raw_value = trimmed[value_start..].trim_end();
+        let raw_value = raw_value
+            .find(" #")
+            .filter(|_| !raw_value.starts_with(['\'', '"']))
+            .map_or(raw_value, |comment_start| {
+                raw_value[..comment_start].trim_end()
+            });
+        if raw_value.is_empty() || raw_value.starts_with(['|', '>', '{', '[', '&', '*', '!']) {
+            return Err(ValueError::NotAString(key.to_owned()));
+        }
+        let start = line_start + indent + value_start;
+        return Ok(start..start + raw_value.len());
+    }
+    Err(ValueError::KeyNotFound(key.to_owned()))
+}
+
+/// Splits a `key: value` line returning the unquoted key and the offset of the value.
+fn yaml_key(line: &str) -> Option<(String, usize)> {
+    let colon = line.find(": ").or_else(|| {
+        line.trim_end()
+            .ends_with(':')
+            .then(|| line.trim_end().len() - 1)
+    })?;
+    let key = yaml_unquote(line[..colon].trim());
+    let value_start = colon + 1 + (line[colon + 1..].len() - line[colon + 1..].trim_start().len());
+    Some((key, value_start))
+}
+
+fn yaml_unquote(raw: &str) -> String {
+    if raw.len() >= 2 && raw.starts_with('\'') && raw.ends_with('\'') {
+        raw[1..raw.len() - 1].replace("''", "'")
+    } else if raw.len() >= 2 && raw.starts_with('"') && raw.ends_with('"') {
+        serde_json::from_str(raw).unwrap_or_else(|_| raw[1..raw.len() - 1].to_owned())
+    } else {
+        raw.to_owned()
+    }
+}
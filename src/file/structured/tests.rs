@@ -0,0 +1,323 @@
+use super::{get_value, insert_value, set_value, ValueError};
+use crate::config::FileFormat;
+
+struct TestCase {
+    format: FileFormat,
+    key: &'static str,
+    content: &'static str,
+    expected_value: Result<&'static str, ValueError>,
+    expected_content: &'static str,
+}
+
+fn assert_test_case(test_case: TestCase) {
+    let value = get_value(test_case.content, &test_case.format, test_case.key);
+    match (&value, &test_case.expected_value) {
+        // Parsing errors are the ones of the underlying parsers
+        (Err(ValueError::Parse(_)), Err(ValueError::Parse(_))) => {}
+        _ => assert_eq!(
+            value,
+            test_case
+                .expected_value
+                .as_ref()
+                .map(|v| v.to_string())
+                .map_err(|err| match err {
+                    ValueError::KeyNotFound(key) => ValueError::KeyNotFound(key.to_owned()),
+                    ValueError::NotAString(key) => ValueError::NotAString(key.to_owned()),
+                    ValueError::Parse(msg) => ValueError::Parse(msg.to_owned()),
+                })
+        ),
+    }
+    if test_case.expected_value.is_ok() {
+        assert_eq!(
+            set_value(test_case.content, &test_case.format, test_case.key, "2.0.0").unwrap(),
+            test_case.expected_content
+        );
+    }
+}
+
+mod test_toml {
+    use super::*;
+
+    #[test]
+    fn test_nested_key() {
+        assert_test_case(TestCase {
+            format: FileFormat::Toml,
+            key: "package.version",
+            content: r#"[package]
+name = "app"
+# The version of the package
+version = '1.0.0'  # bumped by semver
+
+[dependencies]
+lib = { version = "1.0.0" }
+"#,
+            expected_value: Ok("1.0.0"),
+            expected_content: r#"[package]
+name = "app"
+# The version of the package
+version = "2.0.0"  # bumped by semver
+
+[dependencies]
+lib = { version = "1.0.0" }
+"#,
+        });
+    }
+
+    #[test]
+    fn test_inline_table() {
+        assert_test_case(TestCase {
+            format: FileFormat::Toml,
+            key: "dependencies.lib.version",
+            content: "[dependencies]\nlib = { version = \"1.0.0\", path = \"../lib\" }\n",
+            expected_value: Ok("1.0.0"),
+            expected_content: "[dependencies]\nlib = { version = \"2.0.0\", path = \"../lib\" }\n",
+        });
+    }
+
+    #[test]
+    fn test_missing_key() {
+        assert_test_case(TestCase {
+            format: FileFormat::Toml,
+            key: "package.version",
+            content: "[package]\nname = \"app\"\n",
+            expected_value: Err(ValueError::KeyNotFound("package.version".to_owned())),
+            expected_content: "",
+        });
+    }
+
+    #[test]
+    fn test_not_a_string() {
+        assert_test_case(TestCase {
+            format: FileFormat::Toml,
+            key: "package",
+            content: "[package]\nname = \"app\"\n",
+            expected_value: Err(ValueError::NotAString("package".to_owned())),
+            expected_content: "",
+        });
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_test_case(TestCase {
+            format: FileFormat::Toml,
+            key: "version",
+            content: "version = ",
+            expected_value: Err(ValueError::Parse("".to_owned())),
+            expected_content: "",
+        });
+    }
+}
+
+mod test_json {
+    use super::*;
+
+    #[test]
+    fn test_top_level_key() {
+        assert_test_case(TestCase {
+            format: FileFormat::Json,
+            key: "version",
+            content: r#"{
+  "name": "app",
+  "dependencies": {"lib": "1.0.0", "other": [1, {"version": "1.0.0"}]},
+  "version"  :   "1.0.0",
+  "private": true
+}
+"#,
+            expected_value: Ok("1.0.0"),
+            expected_content: r#"{
+  "name": "app",
+  "dependencies": {"lib": "1.0.0", "other": [1, {"version": "1.0.0"}]},
+  "version"  :   "2.0.0",
+  "private": true
+}
+"#,
+        });
+    }
+
+    #[test]
+    fn test_nested_key() {
+        assert_test_case(TestCase {
+            format: FileFormat::Json,
+            key: "packages.\"app\".version",
+            content: r#"{"packages": {"\"app\"": {"version": "1.0.0"}}}"#,
+            expected_value: Ok("1.0.0"),
+            expected_content: r#"{"packages": {"\"app\"": {"version": "2.0.0"}}}"#,
+        });
+    }
+
+    #[test]
+    fn test_missing_key() {
+        assert_test_case(TestCase {
+            format: FileFormat::Json,
+            key: "version",
+            content: r#"{"dependencies": {"version": "1.0.0"}}"#,
+            expected_value: Err(ValueError::KeyNotFound("version".to_owned())),
+            expected_content: "",
+        });
+    }
+
+    #[test]
+    fn test_not_a_string() {
+        assert_test_case(TestCase {
+            format: FileFormat::Json,
+            key: "version",
+            content: r#"{"version": 1}"#,
+            expected_value: Err(ValueError::NotAString("version".to_owned())),
+            expected_content: "",
+        });
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_test_case(TestCase {
+            format: FileFormat::Json,
+            key: "version",
+            content: r#"{"version": "1.0.0""#,
+            expected_value: Err(ValueError::Parse("".to_owned())),
+            expected_content: "",
+        });
+    }
+}
+
+mod test_yaml {
+    use super::*;
+
+    #[test]
+    fn test_top_level_key() {
+        assert_test_case(TestCase {
+            format: FileFormat::Yaml,
+            key: "version",
+            content: r#"apiVersion: v2
+name: app
+dependencies:
+  - name: lib
+    version: 1.0.0
+version: 1.0.0 # bumped by semver
+appVersion: "1.0.0"
+"#,
+            expected_value: Ok("1.0.0"),
+            expected_content: r#"apiVersion: v2
+name: app
+dependencies:
+  - name: lib
+    version: 1.0.0
+version: 2.0.0 # bumped by semver
+appVersion: "1.0.0"
+"#,
+        });
+    }
+
+    #[test]
+    fn test_quoted() {
+        assert_test_case(TestCase {
+            format: FileFormat::Yaml,
+            key: "appVersion",
+            content: "version: 1.0.0\nappVersion: '1.0.0'\n",
+            expected_value: Ok("1.0.0"),
+            expected_content: "version: 1.0.0\nappVersion: '2.0.0'\n",
+        });
+    }
+
+    #[test]
+    fn test_nested_key() {
+        assert_test_case(TestCase {
+            format: FileFormat::Yaml,
+            key: "image.tag",
+            content: r#"other:
+  tag: "1.0.0"
+image:
+  repository: app
+
+  # The tag of the image
+  tag: "1.0.0"
+tag: 1.0.0
+"#,
+            expected_value: Ok("1.0.0"),
+            expected_content: r#"other:
+  tag: "1.0.0"
+image:
+  repository: app
+
+  # The tag of the image
+  tag: "2.0.0"
+tag: 1.0.0
+"#,
+        });
+    }
+
+    #[test]
+    fn test_missing_key() {
+        assert_test_case(TestCase {
+            format: FileFormat::Yaml,
+            key: "image.tag",
+            content: "image:\n  repository: app\ntag: 1.0.0\n",
+            expected_value: Err(ValueError::KeyNotFound("image.tag".to_owned())),
+            expected_content: "",
+        });
+    }
+
+    #[test]
+    fn test_not_a_string() {
+        assert_test_case(TestCase {
+            format: FileFormat::Yaml,
+            key: "image",
+            content: "image:\n  tag: 1.0.0\n",
+            expected_value: Err(ValueError::NotAString("image".to_owned())),
+            expected_content: "",
+        });
+    }
+}
+
+mod test_insert_value {
+    use super::*;
+
+    #[test]
+    fn test_existing_key() {
+        assert_eq!(
+            insert_value("a = '1.0.0' # comment\n", &FileFormat::Toml, "a", "2.0.0"),
+            Ok("a = \"2.0.0\" # comment\n".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_toml() {
+        assert_eq!(
+            insert_value(
+                "[tool.semver]\ncurrent_version = \"1.0.0\"\n\n[tool.semver.files]\n",
+                &FileFormat::Toml,
+                "tool.semver.last_stable_version",
+                "1.0.0"
+            ),
+            Ok("[tool.semver]\ncurrent_version = \"1.0.0\"\nlast_stable_version = \"1.0.0\"\n\n[tool.semver.files]\n".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        for (content, expected) in [
+            (
+                "{\n  \"a\": {\n    \"b\": 1\n  }\n}",
+                "{\n  \"a\": {\n    \"c\": \"2.0.0\",\n    \"b\": 1\n  }\n}",
+            ),
+            (r#"{"a": {"b": 1}}"#, r#"{"a": {"c": "2.0.0", "b": 1}}"#),
+            (r#"{"a": {}}"#, r#"{"a": {"c": "2.0.0"}}"#),
+        ] {
+            assert_eq!(
+                insert_value(content, &FileFormat::Json, "a.c", "2.0.0"),
+                Ok(expected.to_owned())
+            );
+        }
+    }
+
+    #[test]
+    fn test_missing_parent() {
+        assert_eq!(
+            insert_value(r#"{"a": 1}"#, &FileFormat::Json, "a.c", "2.0.0"),
+            Err(ValueError::KeyNotFound("a.c".to_owned()))
+        );
+        assert_eq!(
+            insert_value("[a]\n", &FileFormat::Toml, "b.c", "2.0.0"),
+            Err(ValueError::KeyNotFound("b".to_owned()))
+        );
+    }
+}
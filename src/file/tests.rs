@@ -1,4 +1,4 @@
-use super::{replace_files_contents, FileBumpError};
+use super::{replace_files_contents, write_files_contents, FileBumpError, FileChange};
 use std::collections::HashMap;
 
 use crate::{config, core};
@@ -21,7 +21,7 @@ fn create_versioned_file(
     }
     match abs_path.to_str() {
         Some(path) => Ok(path.to_owned()),
-        None => Err(io::Error::new(io::ErrorKind::Other, "Missing file path")),
+        None => Err(io::Error::other("Missing file path")),
     }
 }
 
@@ -94,6 +94,12 @@ fn test_replace_files_contents_version_not_found() {
                 file_paths.get("file-2").unwrap(),
             )))
         );
+        for file in ["file-1", "file-3"] {
+            assert_eq!(
+                fs::read_to_string(file_paths.get(file).unwrap()).unwrap(),
+                format!("Version: '{}'", current_version)
+            );
+        }
     });
 }
 
@@ -232,3 +238,398 @@ fn test_replace_files_contents_with_file_config_stable_only() {
         }));
     });
 }
+
+#[test]
+fn test_replace_files_contents_with_file_config_regex() {
+    let func_name = "test_replace_files_contents_with_file_config_regex";
+    with_test_dir(func_name, |test_dir_name| {
+        let current_version = core::Version::with_values(1, 2, 3, None);
+        let new_version = core::Version::with_values(1, 3, 0, None);
+        let file_path = create_versioned_file(test_dir_name, "file-1", "").unwrap();
+        fs::write(
+            &file_path,
+            "Version: '1.2.3'\nVersion:   \"1.2.3\"\nVersion: '1.2.34'\n",
+        )
+        .unwrap();
+        let files = HashMap::from([(
+            file_path.to_owned(),
+            config::FileConfig::with_regex(
+                r#"Version:\s+(?P<quote>['"]){current_version}['"]"#.to_string(),
+                r"Version: ${quote}{new_version}${quote}".to_string(),
+            ),
+        )]);
+        assert_eq!(
+            replace_files_contents(&current_version, &new_version, None, &files),
+            Ok(())
+        );
+        assert_eq!(
+            fs::read_to_string(&file_path).unwrap(),
+            "Version: '1.3.0'\nVersion: \"1.3.0\"\nVersion: '1.2.34'\n"
+        );
+    });
+}
+
+#[test]
+fn test_replace_files_contents_with_invalid_regex() {
+    let func_name = "test_replace_files_contents_with_invalid_regex";
+    with_test_dir(func_name, |test_dir_name| {
+        let current_version = core::Version::with_values(1, 2, 3, None);
+        let new_version = core::Version::with_values(1, 3, 0, None);
+        let file_path = create_versioned_file(test_dir_name, "file-1", "1.2.3").unwrap();
+        let files = HashMap::from([(
+            file_path.to_owned(),
+            config::FileConfig::with_regex(
+                "Version: ({current_version}".to_string(),
+                "{new_version}".to_string(),
+            ),
+        )]);
+        assert!(matches!(
+            replace_files_contents(&current_version, &new_version, None, &files),
+            Err(FileBumpError::InvalidTemplate(_))
+        ));
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "Version: '1.2.3'");
+    });
+}
+
+mod test_replacements_scope {
+    use super::*;
+
+    const CONTENT: &str = "name = 'app'\nversion = '1.2.3'\n\n[dep]\nversion = '1.2.3'\n";
+
+    struct TestCase {
+        file_config: config::FileConfig,
+        expected: Result<&'static str, FileBumpError>,
+    }
+
+    fn assert_replacements(func_name: &str, test_case: TestCase) {
+        with_test_dir(func_name, |test_dir_name| {
+            let current_version = core::Version::with_values(1, 2, 3, None);
+            let new_version = core::Version::with_values(1, 3, 0, None);
+            let file_path = create_versioned_file(test_dir_name, "file-1", "").unwrap();
+            fs::write(&file_path, CONTENT).unwrap();
+            let files = HashMap::from([(file_path.to_owned(), test_case.file_config.clone())]);
+            let expected = test_case
+                .expected
+                .as_ref()
+                .map(|_| ())
+                .map_err(|err| match err {
+                    FileBumpError::UnexpectedMatches(msg) => {
+                        FileBumpError::UnexpectedMatches(msg.replace("{file_path}", &file_path))
+                    }
+                    FileBumpError::NoOp(msg) => {
+                        FileBumpError::NoOp(msg.replace("{file_path}", &file_path))
+                    }
+                    _ => panic!("Unsupported expected error"),
+                });
+            assert_eq!(
+                replace_files_contents(&current_version, &new_version, None, &files),
+                expected
+            );
+            let expected_content = test_case.expected.as_ref().copied().unwrap_or(CONTENT);
+            assert_eq!(fs::read_to_string(&file_path).unwrap(), expected_content);
+        });
+    }
+
+    #[test]
+    fn test_first_only() {
+        assert_replacements(
+            "test_replacements_scope_first_only",
+            TestCase {
+                file_config: config::FileConfig {
+                    first_only: Some(true),
+                    ..config::FileConfig::new()
+                },
+                expected: Ok("name = 'app'\nversion = '1.3.0'\n\n[dep]\nversion = '1.2.3'\n"),
+            },
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_replacements(
+            "test_replacements_scope_lines",
+            TestCase {
+                file_config: config::FileConfig {
+                    lines: Some((4, 5)),
+                    ..config::FileConfig::new()
+                },
+                expected: Ok("name = 'app'\nversion = '1.2.3'\n\n[dep]\nversion = '1.3.0'\n"),
+            },
+        );
+    }
+
+    #[test]
+    fn test_lines_out_of_range() {
+        assert_replacements(
+            "test_replacements_scope_lines_out_of_range",
+            TestCase {
+                file_config: config::FileConfig {
+                    lines: Some((10, 20)),
+                    ..config::FileConfig::new()
+                },
+                expected: Err(FileBumpError::NoOp(
+                    "Nothing changed in file '{file_path}'".to_owned(),
+                )),
+            },
+        );
+    }
+
+    #[test]
+    fn test_expected_count() {
+        assert_replacements(
+            "test_replacements_scope_expected_count",
+            TestCase {
+                file_config: config::FileConfig {
+                    expected_count: Some(2),
+                    ..config::FileConfig::new()
+                },
+                expected: Ok("name = 'app'\nversion = '1.3.0'\n\n[dep]\nversion = '1.3.0'\n"),
+            },
+        );
+    }
+
+    #[test]
+    fn test_expected_count_mismatch() {
+        assert_replacements(
+            "test_replacements_scope_expected_count_mismatch",
+            TestCase {
+                file_config: config::FileConfig {
+                    expected_count: Some(1),
+                    ..config::FileConfig::new()
+                },
+                expected: Err(FileBumpError::UnexpectedMatches(
+                    "Found 2 occurrences in file '{file_path}' while expecting exactly 1"
+                        .to_owned(),
+                )),
+            },
+        );
+    }
+
+    #[test]
+    fn test_max_replacements_exceeded() {
+        assert_replacements(
+            "test_replacements_scope_max_replacements_exceeded",
+            TestCase {
+                file_config: config::FileConfig {
+                    max_replacements: Some(1),
+                    ..config::FileConfig::new()
+                },
+                expected: Err(FileBumpError::UnexpectedMatches(
+                    "Found 2 occurrences in file '{file_path}' while expecting at most 1"
+                        .to_owned(),
+                )),
+            },
+        );
+    }
+
+    #[test]
+    fn test_max_replacements_with_lines() {
+        assert_replacements(
+            "test_replacements_scope_max_replacements_with_lines",
+            TestCase {
+                file_config: config::FileConfig {
+                    max_replacements: Some(1),
+                    lines: Some((1, 3)),
+                    ..config::FileConfig::new()
+                },
+                expected: Ok("name = 'app'\nversion = '1.3.0'\n\n[dep]\nversion = '1.2.3'\n"),
+            },
+        );
+    }
+}
+
+#[test]
+fn test_replace_files_contents_with_key() {
+    let func_name = "test_replace_files_contents_with_key";
+    with_test_dir(func_name, |test_dir_name| {
+        let current_version = core::Version::with_values(1, 2, 3, None);
+        let new_version = core::Version::with_values(1, 3, 0, None);
+        let file_path = create_versioned_file(test_dir_name, "file-1", "").unwrap();
+        fs::write(
+            &file_path,
+            "[package]\nversion = \"1.2.3\"\n\n[dependencies]\nlib = \"1.2.3\"\n",
+        )
+        .unwrap();
+        let files = HashMap::from([(
+            file_path.to_owned(),
+            config::FileConfig::with_key(config::FileFormat::Toml, "package.version".to_owned()),
+        )]);
+        assert_eq!(
+            replace_files_contents(&current_version, &new_version, None, &files),
+            Ok(())
+        );
+        assert_eq!(
+            fs::read_to_string(&file_path).unwrap(),
+            "[package]\nversion = \"1.3.0\"\n\n[dependencies]\nlib = \"1.2.3\"\n"
+        );
+    });
+}
+
+#[test]
+fn test_replace_files_contents_with_key_unexpected_value() {
+    let func_name = "test_replace_files_contents_with_key_unexpected_value";
+    with_test_dir(func_name, |test_dir_name| {
+        let current_version = core::Version::with_values(1, 2, 3, None);
+        let new_version = core::Version::with_values(1, 3, 0, None);
+        let file_path = create_versioned_file(test_dir_name, "file-1", "").unwrap();
+        fs::write(&file_path, r#"{"version": "1.0.0", "lib": "1.2.3"}"#).unwrap();
+        let files = HashMap::from([(
+            file_path.to_owned(),
+            config::FileConfig::with_key(config::FileFormat::Json, "version".to_owned()),
+        )]);
+        assert_eq!(
+            replace_files_contents(&current_version, &new_version, None, &files),
+            Err(FileBumpError::UnexpectedMatches(format!(
+                "Found '1.0.0' at key 'version' in file '{}' while expecting '1.2.3'",
+                file_path
+            )))
+        );
+    });
+}
+
+#[test]
+fn test_replace_files_contents_with_missing_key() {
+    let func_name = "test_replace_files_contents_with_missing_key";
+    with_test_dir(func_name, |test_dir_name| {
+        let current_version = core::Version::with_values(1, 2, 3, None);
+        let new_version = core::Version::with_values(1, 3, 0, None);
+        let file_path = create_versioned_file(test_dir_name, "file-1", "").unwrap();
+        fs::write(&file_path, "name: app\n").unwrap();
+        let files = HashMap::from([(
+            file_path.to_owned(),
+            config::FileConfig::with_key(config::FileFormat::Yaml, "version".to_owned()),
+        )]);
+        assert_eq!(
+            replace_files_contents(&current_version, &new_version, None, &files),
+            Err(FileBumpError::InvalidContent(format!(
+                "Key 'version' not found in file '{}'",
+                file_path
+            )))
+        );
+    });
+}
+
+#[test]
+fn test_write_files_contents() {
+    let func_name = "test_write_files_contents";
+    with_test_dir(func_name, |test_dir_name| {
+        let changes = ["file-1", "file-2"].map(|f| FileChange {
+            path: create_versioned_file(test_dir_name, f, "1.0.0").unwrap(),
+            original_content: "Version: '1.0.0'".to_owned(),
+            new_content: "Version: '2.0.0'".to_owned(),
+            matches: 1,
+        });
+        assert_eq!(write_files_contents(&changes), Ok(()));
+        for change in &changes {
+            assert_eq!(
+                fs::read_to_string(&change.path).unwrap(),
+                "Version: '2.0.0'"
+            );
+        }
+        assert_eq!(fs::read_dir(test_dir_name).unwrap().count(), 2);
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_files_contents_symlink() {
+    let func_name = "test_write_files_contents_symlink";
+    with_test_dir(func_name, |test_dir_name| {
+        let file_path = create_versioned_file(test_dir_name, "file-1", "1.0.0").unwrap();
+        let link_path = format!("{}/link", test_dir_name);
+        std::os::unix::fs::symlink(fs::canonicalize(&file_path).unwrap(), &link_path).unwrap();
+        let changes = [FileChange {
+            path: link_path.to_owned(),
+            original_content: "Version: '1.0.0'".to_owned(),
+            new_content: "Version: '2.0.0'".to_owned(),
+            matches: 1,
+        }];
+        assert_eq!(write_files_contents(&changes), Ok(()));
+        assert!(fs::symlink_metadata(&link_path)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "Version: '2.0.0'");
+        assert_eq!(fs::read_dir(test_dir_name).unwrap().count(), 2);
+    });
+}
+
+#[test]
+fn test_write_files_contents_rollback() {
+    let func_name = "test_write_files_contents_rollback";
+    with_test_dir(func_name, |test_dir_name| {
+        let file_path = create_versioned_file(test_dir_name, "file-1", "1.0.0").unwrap();
+        // Renaming a file over a non-empty directory fails after the first file is written.
+        let dir_path = format!("{}/dir", test_dir_name);
+        fs::create_dir_all(format!("{}/nested", dir_path)).unwrap();
+        let changes = [
+            FileChange {
+                path: file_path.to_owned(),
+                original_content: "Version: '1.0.0'".to_owned(),
+                new_content: "Version: '2.0.0'".to_owned(),
+                matches: 1,
+            },
+            FileChange {
+                path: dir_path,
+                original_content: "".to_owned(),
+                new_content: "Version: '2.0.0'".to_owned(),
+                matches: 1,
+            },
+        ];
+        assert!(matches!(
+            write_files_contents(&changes),
+            Err(FileBumpError::Io(_))
+        ));
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "Version: '1.0.0'");
+        assert_eq!(fs::read_dir(test_dir_name).unwrap().count(), 2);
+    });
+}
+
+#[test]
+fn test_write_files_contents_missing_dir() {
+    let func_name = "test_write_files_contents_missing_dir";
+    with_test_dir(func_name, |test_dir_name| {
+        let file_path = create_versioned_file(test_dir_name, "file-1", "1.0.0").unwrap();
+        let changes = [
+            FileChange {
+                path: file_path.to_owned(),
+                original_content: "Version: '1.0.0'".to_owned(),
+                new_content: "Version: '2.0.0'".to_owned(),
+                matches: 1,
+            },
+            FileChange {
+                path: format!("{}/missing/test-file_file-2", test_dir_name),
+                original_content: "Version: '1.0.0'".to_owned(),
+                new_content: "Version: '2.0.0'".to_owned(),
+                matches: 1,
+            },
+        ];
+        assert!(matches!(
+            write_files_contents(&changes),
+            Err(FileBumpError::Io(_))
+        ));
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "Version: '1.0.0'");
+        assert_eq!(fs::read_dir(test_dir_name).unwrap().count(), 1);
+    });
+}
+
+#[test]
+fn test_file_change_unified_diff() {
+    let change = FileChange {
+        path: "Cargo.toml".to_owned(),
+        original_content: "[package]\nname = \"semver\"\nversion = \"1.0.0\"\n".to_owned(),
+        new_content: "[package]\nname = \"semver\"\nversion = \"1.1.0\"\n".to_owned(),
+        matches: 1,
+    };
+    assert_eq!(
+        change.unified_diff(),
+        r#"--- Cargo.toml
++++ Cargo.toml
+@@ -1,3 +1,3 @@
+ [package]
+ name = "semver"
+-version = "1.0.0"
++version = "1.1.0"
+"#
+    );
+}
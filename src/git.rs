@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod tests;
+use crate::{config, template};
+use std::path::Path;
+use std::process::Command;
+use std::{fmt, io};
+
+#[derive(Debug)]
+pub enum GitError {
+    Io(io::Error),
+    InvalidTemplate(String),
+    InvalidState(String),
+    CommandFailed(String),
+}
+
+impl From<io::Error> for GitError {
+    fn from(err: io::Error) -> GitError {
+        GitError::Io(err)
+    }
+}
+
+impl fmt::Display for GitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GitError::Io(err) => write!(f, "Cannot run git: {}", err),
+            GitError::InvalidTemplate(msg) => write!(f, "{}", msg),
+            GitError::InvalidState(msg) => write!(f, "{}", msg),
+            GitError::CommandFailed(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for GitError {}
+
+impl PartialEq for GitError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (GitError::InvalidTemplate(a), GitError::InvalidTemplate(b)) => a == b,
+            (GitError::InvalidState(a), GitError::InvalidState(b)) => a == b,
+            (GitError::CommandFailed(a), GitError::CommandFailed(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// The commit and the tag of a bump, with their templates rendered.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Release {
+    pub message: String,
+    pub tag_name: Option<String>,
+}
+
+/// Renders the message and the name of the tag according to `options`, returning `None` if
+/// nothing has to be committed, and checks that they can be committed and tagged in `dir`.
+///
+/// This is meant to be called before writing any file, to not leave a bump half-released. Tagging
+/// implies committing.
+pub fn prepare(
+    dir: &Path,
+    options: &config::GitOptions,
+    context: &template::Context,
+) -> Result<Option<Release>, GitError> {
+    if !options.commit && !options.tag {
+        return Ok(None);
+    }
+    let render = |name: &str, git_template: &str| {
+        template::render(git_template, context)
+            .map_err(|err| GitError::InvalidTemplate(format!("Invalid {}: {}", name, err)))
+    };
+    let message = render("commit message", &options.message)?;
+    let tag_name = if options.tag {
+        Some(render("tag name", &options.tag_name)?)
+    } else {
+        None
+    };
+
+    let inside_work_tree = query(dir, &["rev-parse", "--is-inside-work-tree"])?;
+    if inside_work_tree.as_deref() != Some("true") {
+        return Err(GitError::InvalidState(format!(
+            "Cannot commit the bump, '{}' is not inside a git work tree",
+            dir.display()
+        )));
+    }
+    if let Some(tag_name) = &tag_name {
+        if !is_valid_tag_name(tag_name)? {
+            return Err(GitError::InvalidState(format!(
+                "Cannot tag the bump, '{}' is not a valid tag name",
+                tag_name
+            )));
+        }
+        let tag_ref = format!("refs/tags/{}", tag_name);
+        if query(dir, &["rev-parse", "--verify", "-q", &tag_ref])?.is_some() {
+            return Err(GitError::InvalidState(format!(
+                "Cannot tag the bump, tag '{}' already exists",
+                tag_name
+            )));
+        }
+    }
+    Ok(Some(Release { message, tag_name }))
+}
+
+/// Checks whether `name` can be the name of a tag, which doesn't require a repository.
+pub fn is_valid_tag_name(name: &str) -> Result<bool, GitError> {
+    let tag_ref = format!("refs/tags/{}", name);
+    Ok(query(Path::new("."), &["check-ref-format", &tag_ref])?.is_some())
+}
+
+/// Commits the files at `paths` of the repository in `dir` and tags the commit if required.
+pub fn commit_and_tag(dir: &Path, paths: &[&str], release: &Release) -> Result<(), GitError> {
+    commit(dir, paths, &release.message)?;
+    if let Some(tag_name) = &release.tag_name {
+        tag(dir, tag_name, &release.message)?;
+    }
+    Ok(())
+}
+
+/// Stages and commits exactly the files at `paths`, leaving out anything else already staged.
+pub fn commit(dir: &Path, paths: &[&str], message: &str) -> Result<(), GitError> {
+    run(dir, &[&["add", "--"], paths].concat())?;
+    run(
+        dir,
+        &[&["commit", "--only", "-m", message, "--"], paths].concat(),
+    )
+}
+
+/// Creates an annotated tag of the current commit.
+pub fn tag(dir: &Path, name: &str, message: &str) -> Result<(), GitError> {
+    run(dir, &["tag", "-a", name, "-m", message])
+}
+
+/// Runs a git command whose failure is an answer rather than an error, returning its output if
+/// it succeeds.
+fn query(dir: &Path, args: &[&str]) -> Result<Option<String>, GitError> {
+    let output = Command::new("git").args(args).current_dir(dir).output()?;
+    Ok(output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_owned()))
+}
+
+fn run(dir: &Path, args: &[&str]) -> Result<(), GitError> {
+    let output = Command::new("git").args(args).current_dir(dir).output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let details = match stderr.trim() {
+        "" => String::new(),
+        stderr => format!(": {}", stderr),
+    };
+    Err(GitError::CommandFailed(format!(
+        "Command 'git {}' failed with {}{}",
+        args[0], output.status, details
+    )))
+}
@@ -0,0 +1,169 @@
+use super::{commit_and_tag, prepare, GitError, Release};
+use crate::config::GitOptions;
+use crate::template::Context;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const TEST_DIR_BASE_NAME: &str = "./__";
+
+fn with_test_repo<F>(test_func_name: &str, test_func: F)
+where
+    F: Fn(&Path),
+{
+    let test_dir_name = format!("{}{}", TEST_DIR_BASE_NAME, test_func_name);
+    fs::create_dir_all(&test_dir_name).unwrap();
+    let dir = Path::new(&test_dir_name);
+    for args in [
+        vec!["init", "-q"],
+        vec!["config", "user.name", "semver"],
+        vec!["config", "user.email", "semver@example.com"],
+        vec!["config", "commit.gpgsign", "false"],
+        vec!["config", "tag.gpgsign", "false"],
+    ] {
+        git(dir, &args);
+    }
+    test_func(dir);
+    fs::remove_dir_all(test_dir_name).unwrap();
+}
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    String::from_utf8(output.stdout).unwrap().trim().to_owned()
+}
+
+fn context() -> Context {
+    Context::with_versions("1.0.0".to_owned(), "1.1.0".to_owned(), "1.0.0".to_owned())
+}
+
+#[test]
+fn test_commit_and_tag() {
+    with_test_repo("test_commit_and_tag", |dir| {
+        fs::write(dir.join("VERSION"), "1.1.0").unwrap();
+        fs::write(dir.join("staged.txt"), "").unwrap();
+        git(dir, &["add", "staged.txt"]);
+
+        let options = GitOptions {
+            commit: true,
+            tag: true,
+            ..GitOptions::default()
+        };
+        let release = prepare(dir, &options, &context()).unwrap().unwrap();
+        assert_eq!(
+            release,
+            Release {
+                message: "Bump version: 1.0.0 → 1.1.0".to_owned(),
+                tag_name: Some("v1.1.0".to_owned()),
+            }
+        );
+        commit_and_tag(dir, &["VERSION"], &release).unwrap();
+
+        assert_eq!(
+            git(dir, &["log", "-1", "--format=%s"]),
+            "Bump version: 1.0.0 → 1.1.0"
+        );
+        assert_eq!(git(dir, &["show", "--name-only", "--format="]), "VERSION");
+        // Files staged before the bump are left out
+        assert_eq!(git(dir, &["diff", "--cached", "--name-only"]), "staged.txt");
+        assert_eq!(git(dir, &["cat-file", "-t", "v1.1.0"]), "tag");
+        assert_eq!(
+            git(
+                dir,
+                &["tag", "-l", "--format=%(contents:subject)", "v1.1.0"]
+            ),
+            "Bump version: 1.0.0 → 1.1.0"
+        );
+    });
+}
+
+#[test]
+fn test_commit_only() {
+    with_test_repo("test_commit_only", |dir| {
+        fs::write(dir.join("VERSION"), "1.1.0").unwrap();
+
+        let options = GitOptions {
+            commit: true,
+            message: "Release {new_version}".to_owned(),
+            ..GitOptions::default()
+        };
+        let release = prepare(dir, &options, &context()).unwrap().unwrap();
+        assert_eq!(release.tag_name, None);
+        commit_and_tag(dir, &["VERSION"], &release).unwrap();
+        assert_eq!(git(dir, &["log", "-1", "--format=%s"]), "Release 1.1.0");
+        assert_eq!(git(dir, &["tag", "-l"]), "");
+    });
+}
+
+#[test]
+fn test_disabled() {
+    with_test_repo("test_disabled", |dir| {
+        fs::write(dir.join("VERSION"), "1.1.0").unwrap();
+
+        assert_eq!(
+            prepare(dir, &GitOptions::default(), &context()).unwrap(),
+            None
+        );
+    });
+}
+
+#[test]
+fn test_errors() {
+    with_test_repo("test_errors", |dir| {
+        let tag_options = GitOptions {
+            tag: true,
+            ..GitOptions::default()
+        };
+        let options = GitOptions {
+            tag_name: "v{unknown}".to_owned(),
+            ..tag_options.to_owned()
+        };
+        assert!(matches!(
+            prepare(dir, &options, &context()),
+            Err(GitError::InvalidTemplate(msg)) if msg.starts_with("Invalid tag name: ")
+        ));
+
+        // The git directory itself is not inside the work tree
+        assert_eq!(
+            prepare(&dir.join(".git"), &tag_options, &context()),
+            Err(GitError::InvalidState(format!(
+                "Cannot commit the bump, '{}' is not inside a git work tree",
+                dir.join(".git").display()
+            )))
+        );
+
+        let options = GitOptions {
+            tag_name: "release {new_version}".to_owned(),
+            ..tag_options.to_owned()
+        };
+        assert_eq!(
+            prepare(dir, &options, &context()),
+            Err(GitError::InvalidState(
+                "Cannot tag the bump, 'release 1.1.0' is not a valid tag name".to_owned()
+            ))
+        );
+
+        fs::write(dir.join("VERSION"), "1.0.0").unwrap();
+        git(dir, &["add", "VERSION"]);
+        git(dir, &["commit", "-q", "-m", "Initial commit"]);
+        git(dir, &["tag", "v1.1.0"]);
+        assert_eq!(
+            prepare(dir, &tag_options, &context()),
+            Err(GitError::InvalidState(
+                "Cannot tag the bump, tag 'v1.1.0' already exists".to_owned()
+            ))
+        );
+
+        let release = Release {
+            message: "Bump version: 1.0.0 → 1.1.0".to_owned(),
+            tag_name: None,
+        };
+        assert!(matches!(
+            commit_and_tag(dir, &["missing.txt"], &release),
+            Err(GitError::CommandFailed(msg)) if msg.starts_with("Command 'git add' failed with ")
+        ));
+    });
+}
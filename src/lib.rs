@@ -2,6 +2,7 @@ pub mod cmd;
 pub mod config;
 pub mod core;
 pub mod file;
+pub mod git;
 pub mod template;
 
 #[cfg(test)]
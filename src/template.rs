@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests;
 use chrono::prelude::{Date, Local, Utc};
+use regex::Regex;
 use serde::Serialize;
 use std::error::Error;
 use tinytemplate::TinyTemplate;
@@ -44,6 +45,22 @@ impl Context {
             local_today_ymd: local_today.format("%Y-%m-%d").to_string(),
         }
     }
+
+    fn regex_escaped(&self) -> Self {
+        Context {
+            current_version: regex::escape(&self.current_version),
+            new_version: regex::escape(&self.new_version),
+            last_stable_version: regex::escape(&self.last_stable_version),
+            utc_today_ymd: regex::escape(&self.utc_today_ymd),
+            local_today_ymd: regex::escape(&self.local_today_ymd),
+        }
+    }
+}
+
+pub fn render<C: Serialize>(template: &str, context: &C) -> Result<String, Box<dyn Error>> {
+    let mut tt = TinyTemplate::new();
+    tt.add_template("template", template)?;
+    Ok(tt.render("template", context)?)
 }
 
 pub fn replace_content(
@@ -52,6 +69,19 @@ pub fn replace_content(
     replace: &str,
     context: &Context,
 ) -> Result<String, Box<dyn Error>> {
+    replace_content_with_count(content, search, replace, context, None)
+        .map(|(replaced, _)| replaced)
+}
+
+/// Like `replace_content`, but replaces at most `limit` occurrences, if given, and also returns
+/// the number of occurrences found.
+pub fn replace_content_with_count(
+    content: &str,
+    search: &str,
+    replace: &str,
+    context: &Context,
+    limit: Option<usize>,
+) -> Result<(String, usize), Box<dyn Error>> {
     let mut tt = TinyTemplate::new();
     tt.add_template("search", search)?;
     tt.add_template("replace", replace)?;
@@ -59,5 +89,36 @@ pub fn replace_content(
     let rendered_search = tt.render("search", context)?;
     let rendered_replace = tt.render("replace", context)?;
 
-    Ok(content.replace(&rendered_search, &rendered_replace))
+    let count = content.matches(&rendered_search).count();
+    let replaced = match limit {
+        Some(limit) => content.replacen(&rendered_search, &rendered_replace, limit),
+        None => content.replace(&rendered_search, &rendered_replace),
+    };
+    Ok((replaced, count))
+}
+
+/// Like `replace_content_with_count`, but `search` is rendered into a regex, with the variables
+/// escaped, and `replace` can reference its capture groups (e.g. `$1` or `${name}`).
+///
+/// Literal braces in `search`, like in quantifiers, must be escaped as `\{` to not be taken as
+/// variables.
+pub fn replace_regex_with_count(
+    content: &str,
+    search: &str,
+    replace: &str,
+    context: &Context,
+    limit: Option<usize>,
+) -> Result<(String, usize), Box<dyn Error>> {
+    let rendered_search = render(search, &context.regex_escaped())?;
+    // `${name}` refers to a capture group, not to a variable
+    let rendered_replace = render(&replace.replace("${", "$\\{"), context)?;
+
+    let re = Regex::new(&rendered_search)?;
+    let count = re.find_iter(content).count();
+    Ok((
+        // A limit of 0 replaces all the occurrences
+        re.replacen(content, limit.unwrap_or(0), rendered_replace.as_str())
+            .into_owned(),
+        count,
+    ))
 }
@@ -1,4 +1,4 @@
-use super::{replace_content, Context};
+use super::{replace_content, replace_content_with_count, replace_regex_with_count, Context};
 use chrono::prelude::*;
 
 #[test]
@@ -69,3 +69,118 @@ fn test_replace_content_with_date() {
 "#
     );
 }
+
+#[test]
+fn test_replace_content_with_count() {
+    let content = r#"
+semver = 1.0.0
+other = 1.0.0
+"#;
+    let context =
+        Context::with_versions("1.0.0".to_owned(), "2.0.0".to_owned(), "1.0.0".to_owned());
+
+    assert_eq!(
+        replace_content_with_count(
+            content,
+            "{current_version}",
+            "{new_version}",
+            &context,
+            None
+        )
+        .unwrap(),
+        (
+            r#"
+semver = 2.0.0
+other = 2.0.0
+"#
+            .to_owned(),
+            2
+        )
+    );
+}
+
+#[test]
+fn test_replace_regex_with_count() {
+    let content = r#"
+version = "1.0.0+build.1"
+image: app:1.0.0+build.1
+other = "1.0.0+build.1"
+"#;
+    let search = r#"(?m)^(?P<key>version =|image: app:) ?"?{current_version}"?$"#;
+    let replace = r#"${key} "{new_version}""#;
+    let context = Context::with_versions(
+        "1.0.0+build.1".to_owned(),
+        "2.0.0".to_owned(),
+        "1.0.0".to_owned(),
+    );
+
+    assert_eq!(
+        replace_regex_with_count(content, search, replace, &context, None).unwrap(),
+        (
+            r#"
+version = "2.0.0"
+image: app: "2.0.0"
+other = "1.0.0+build.1"
+"#
+            .to_owned(),
+            2
+        )
+    );
+}
+
+#[test]
+fn test_replace_regex_with_count_escaped_braces() {
+    let content = "released 1.0.0 on 2022-01-01";
+    let search = r"{current_version} on \d\{4}-\d\{2}-\d\{2}";
+    let replace = "{new_version} on {utc_today_ymd}";
+    let context = Context::with_versions_and_now(
+        "1.0.0".to_owned(),
+        "2.0.0".to_owned(),
+        "1.0.0".to_owned(),
+        Utc.ymd(2022, 6, 10),
+        Local.ymd(2022, 6, 10),
+    );
+
+    assert_eq!(
+        replace_regex_with_count(content, search, replace, &context, None).unwrap(),
+        ("released 2.0.0 on 2022-06-10".to_owned(), 1)
+    );
+}
+
+#[test]
+fn test_replace_regex_with_count_invalid_regex() {
+    let context =
+        Context::with_versions("1.0.0".to_owned(), "2.0.0".to_owned(), "1.0.0".to_owned());
+
+    assert!(replace_regex_with_count("1.0.0", "({current_version}", "", &context, None).is_err());
+}
+
+#[test]
+fn test_replace_with_count_limit() {
+    let content = "1.0.0 1.0.0 1.0.0";
+    let context =
+        Context::with_versions("1.0.0".to_owned(), "2.0.0".to_owned(), "1.0.0".to_owned());
+
+    assert_eq!(
+        replace_content_with_count(
+            content,
+            "{current_version}",
+            "{new_version}",
+            &context,
+            Some(1)
+        )
+        .unwrap(),
+        ("2.0.0 1.0.0 1.0.0".to_owned(), 3)
+    );
+    assert_eq!(
+        replace_regex_with_count(
+            content,
+            "{current_version}",
+            "{new_version}",
+            &context,
+            Some(2)
+        )
+        .unwrap(),
+        ("2.0.0 2.0.0 1.0.0".to_owned(), 3)
+    );
+}
@@ -11,3 +11,13 @@ pub fn v2() -> Version {
 pub fn v3() -> Version {
     Version::with_values(30, 20, 10, Some("dev.5".to_owned()))
 }
+
+pub fn v4() -> Version {
+    Version::with_values_and_buildmetadata(
+        1,
+        2,
+        3,
+        Some("dev.5".to_owned()),
+        Some("build.1".to_owned()),
+    )
+}